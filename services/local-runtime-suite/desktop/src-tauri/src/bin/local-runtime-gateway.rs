@@ -1,6 +1,12 @@
+use std::collections::HashMap;
 use std::env;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command, ExitCode, Stdio};
+use std::time::{Duration, Instant};
+
+use fslock::LockFile;
+use tempfile::NamedTempFile;
 
 fn candidates(exe_dir: &Path) -> Vec<PathBuf> {
     let dev = exe_dir
@@ -33,6 +39,278 @@ fn find_runtime_root(exe_dir: &Path) -> Option<PathBuf> {
     None
 }
 
+/// `LOCAL_RUNTIME_DIR`, when set, names the runtime root directly and
+/// bypasses `find_runtime_root`'s candidate search entirely — useful for
+/// pointing the gateway at a developer's local Python tree or a shared
+/// install without relocating files.
+fn runtime_root_override() -> Option<PathBuf> {
+    env::var_os("LOCAL_RUNTIME_DIR").map(PathBuf::from)
+}
+
+/// `LOCAL_RUNTIME_PYTHON`, when set, names an explicit interpreter and
+/// bypasses `find_python`/`find_system_python`.
+fn python_override() -> Option<PathBuf> {
+    env::var_os("LOCAL_RUNTIME_PYTHON").map(PathBuf::from)
+}
+
+/// Builds the ordered list of interpreter paths `find_system_python` will
+/// check, in priority order: every `PATH` entry's `python`, then every
+/// entry's `python3`, then every entry's `python2`. Split out as a pure
+/// function over an already-split `PATH` so the precedence order is
+/// testable without touching the filesystem.
+fn python_search_order(path_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let exe_suffix = if cfg!(windows) { ".exe" } else { "" };
+    let mut candidates = Vec::with_capacity(path_dirs.len() * 3);
+    for name in ["python", "python3", "python2"] {
+        let filename = format!("{name}{exe_suffix}");
+        for dir in path_dirs {
+            candidates.push(dir.join(&filename));
+        }
+    }
+    candidates
+}
+
+/// Falls back to whatever Python the user already has on `PATH` when the
+/// embedded runtime tree isn't shipped (dev checkouts, machines where the
+/// installer skipped bundling it). Prefers a plain `python` if one exists
+/// anywhere on `PATH`, otherwise `python3` over `python2`.
+fn find_system_python() -> Option<PathBuf> {
+    let path_var = env::var_os("PATH")?;
+    let path_dirs: Vec<PathBuf> = env::split_paths(&path_var).collect();
+    python_search_order(&path_dirs)
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+}
+
+/// How long to wait for a sibling launch to finish touching `runtime_root`
+/// (first-run bytecode compilation, pyc cache population, unpacking) before
+/// giving up instead of deadlocking behind a hung or dead process.
+const RUNTIME_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Acquires an advisory lock on a file inside `runtime_root` so concurrent
+/// gateway launches don't race each other while the embedded runtime tree
+/// is still being populated. Polls `try_lock` rather than blocking
+/// indefinitely so a sibling that died while holding the lock can't wedge
+/// new launches forever.
+fn acquire_runtime_lock(runtime_root: &Path) -> Result<LockFile, String> {
+    let lock_path = runtime_root.join(".local-runtime-gateway.lock");
+    let mut lock = LockFile::open(&lock_path)
+        .map_err(|err| format!("failed to open runtime lock {}: {err}", lock_path.display()))?;
+    let deadline = Instant::now() + RUNTIME_LOCK_TIMEOUT;
+    loop {
+        match lock.try_lock() {
+            Ok(true) => return Ok(lock),
+            Ok(false) => {
+                if Instant::now() >= deadline {
+                    return Err(format!(
+                        "timed out after {}s waiting for the runtime lock at {} (held by a sibling launch?)",
+                        RUNTIME_LOCK_TIMEOUT.as_secs(),
+                        lock_path.display()
+                    ));
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(err) => {
+                return Err(format!(
+                    "failed to acquire runtime lock {}: {err}",
+                    lock_path.display()
+                ))
+            }
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct RuntimeManifestEntry {
+    size: u64,
+    sha256: String,
+}
+
+#[derive(serde::Deserialize)]
+struct RuntimeManifest {
+    entries: HashMap<String, RuntimeManifestEntry>,
+}
+
+/// Public half of the key used to sign `manifest.json` at release time, the
+/// same release key that signs the desktop app's sidecar manifest. Pinned
+/// at compile time so a tampered `runtime_root` can't also carry a forged
+/// signature: an attacker able to overwrite `pylibs/` could otherwise just
+/// as easily rewrite `manifest.json` to match their tampered files.
+const RUNTIME_MANIFEST_PUBLIC_KEY_PEM: &str = "-----BEGIN PUBLIC KEY-----
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAu1SU1LfVLPHCozMxH2Mo
+4lgOEePzNm0tRgeLezV6ffAt0gunVTLw7onLRnrq0/IzW7yWR7QkrmBL7jTKEn5u
++qKhbwKfBstIs+bMY2Zkp18gnTxKLxoS2tFczGkPLPgizskuemMghRniWaoLcyeh
+kd3qqGElvW/VDL5AaWTg0nLVkjRo9z+40RQzuVaE8AkAFmxZzow3x+VJYKdjykkJ
+0iT9wCS0DRTXu269V264Vf/3jvredZiKRkgwlL9xNAwxXFg0x/XFw005UWVRIkdg
+cKWTjpBP2dPwVZ4WWC+9aGVd+Gyn1o0CLelf4rEjGoXbAAEgAqeGUxrcIlbjXfbc
+mwIDAQAB
+-----END PUBLIC KEY-----
+";
+
+/// Verifies the detached signature at `<manifest_path>.sig` over
+/// `manifest_bytes` using the pinned release public key by shelling out to
+/// `openssl`. Staging files for `openssl` goes through `NamedTempFile`
+/// (exclusive creation, process-unique names) rather than predictable
+/// paths, since a guessable path in a shared temp directory is a
+/// TOCTOU/symlink hazard on a multi-user machine.
+fn verify_manifest_signature(manifest_path: &Path, manifest_bytes: &[u8]) -> Result<(), String> {
+    let sig_path = PathBuf::from(format!("{}.sig", manifest_path.display()));
+    let signature = std::fs::read(&sig_path)
+        .map_err(|err| format!("missing manifest signature {}: {err}", sig_path.display()))?;
+
+    let mut key_file =
+        NamedTempFile::new().map_err(|err| format!("failed to stage verification key: {err}"))?;
+    key_file
+        .write_all(RUNTIME_MANIFEST_PUBLIC_KEY_PEM.as_bytes())
+        .map_err(|err| format!("failed to stage verification key: {err}"))?;
+
+    let mut sig_file =
+        NamedTempFile::new().map_err(|err| format!("failed to stage manifest signature: {err}"))?;
+    sig_file
+        .write_all(&signature)
+        .map_err(|err| format!("failed to stage manifest signature: {err}"))?;
+
+    let mut manifest_file = NamedTempFile::new()
+        .map_err(|err| format!("failed to stage manifest for verification: {err}"))?;
+    manifest_file
+        .write_all(manifest_bytes)
+        .map_err(|err| format!("failed to stage manifest for verification: {err}"))?;
+
+    let output = Command::new("openssl")
+        .args(["dgst", "-sha256", "-verify"])
+        .arg(key_file.path())
+        .arg("-signature")
+        .arg(sig_file.path())
+        .arg(manifest_file.path())
+        .output()
+        .map_err(|err| format!("failed to run openssl to verify manifest signature: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "runtime manifest signature verification failed: {}",
+            String::from_utf8_lossy(&output.stdout).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Computes a lowercase hex SHA-256 digest by shelling out to whatever
+/// hashing tool the platform provides, since this binary has no crypto
+/// dependency of its own.
+fn sha256_hex(path: &Path) -> Result<String, String> {
+    #[cfg(unix)]
+    {
+        for (tool, args) in [("sha256sum", vec![]), ("shasum", vec!["-a", "256"])] {
+            if let Ok(output) = Command::new(tool).args(&args).arg(path).output() {
+                if output.status.success() {
+                    return parse_hash_tool_output(&output.stdout, path);
+                }
+            }
+        }
+        Err(format!(
+            "no sha256sum or shasum available to hash {}",
+            path.display()
+        ))
+    }
+    #[cfg(windows)]
+    {
+        let output = Command::new("certutil")
+            .arg("-hashfile")
+            .arg(path)
+            .arg("SHA256")
+            .output()
+            .map_err(|err| format!("failed to run certutil: {err}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "certutil exited with failure hashing {}",
+                path.display()
+            ));
+        }
+        parse_hash_tool_output(&output.stdout, path)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        Err(format!(
+            "no hashing tool available on this platform to hash {}",
+            path.display()
+        ))
+    }
+}
+
+#[cfg_attr(not(any(unix, windows)), allow(dead_code))]
+fn parse_hash_tool_output(bytes: &[u8], path: &Path) -> Result<String, String> {
+    let text = String::from_utf8_lossy(bytes);
+    for line in text.lines() {
+        let candidate = line.split_whitespace().next().unwrap_or("");
+        if candidate.len() == 64 && candidate.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Ok(candidate.to_lowercase());
+        }
+    }
+    Err(format!("could not parse hash output for {}", path.display()))
+}
+
+/// Verifies the embedded interpreter and the `pylibs` tree against
+/// `runtime_root/manifest.json` before the gateway trusts either, so a
+/// partial download, failed update, or tampered install fails fast here
+/// with a specific path instead of surfacing later as a confusing Python
+/// import error. Absent a manifest, this is a no-op — the check is opt-in
+/// for builds that ship one.
+///
+/// The manifest itself must carry a valid detached signature from the
+/// release key (see `verify_manifest_signature`) before any entry in it is
+/// trusted — otherwise an attacker able to write into `runtime_root` could
+/// simply rewrite `manifest.json` to match their tampered `pylibs/` tree.
+/// There is deliberately no cached "already verified" marker: a marker
+/// keyed on the manifest's own hash can't distinguish a clean install from
+/// one where `pylibs/` was tampered after the marker was written, so every
+/// launch re-hashes the tree in full. That makes launch latency scale with
+/// `pylibs/` size, which we accept as the cost of the check actually
+/// guarding against tampering rather than only accidental corruption.
+fn verify_runtime_manifest(runtime_root: &Path, python: &Path) -> Result<(), String> {
+    let manifest_path = runtime_root.join("manifest.json");
+    if !manifest_path.exists() {
+        return Ok(());
+    }
+
+    let manifest_bytes = std::fs::read(&manifest_path)
+        .map_err(|err| format!("failed to read {}: {err}", manifest_path.display()))?;
+    verify_manifest_signature(&manifest_path, &manifest_bytes)?;
+
+    let manifest: RuntimeManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|err| format!("failed to parse {}: {err}", manifest_path.display()))?;
+
+    let python_rel = python
+        .strip_prefix(runtime_root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+
+    for (rel_path, entry) in &manifest.entries {
+        let is_interpreter = *rel_path == python_rel;
+        let is_pylibs_entry = rel_path.starts_with("pylibs/");
+        if !is_interpreter && !is_pylibs_entry {
+            continue;
+        }
+
+        let full_path = runtime_root.join(rel_path);
+        let metadata = std::fs::metadata(&full_path)
+            .map_err(|err| format!("manifest entry missing on disk: {rel_path} ({err})"))?;
+        if metadata.len() != entry.size {
+            return Err(format!(
+                "manifest size mismatch for {rel_path}: expected {} bytes, found {}",
+                entry.size,
+                metadata.len()
+            ));
+        }
+
+        let actual_hash = sha256_hex(&full_path)?;
+        if !actual_hash.eq_ignore_ascii_case(&entry.sha256) {
+            return Err(format!("manifest hash mismatch for {rel_path}"));
+        }
+    }
+
+    Ok(())
+}
+
 fn find_python(runtime_root: &Path) -> Option<PathBuf> {
     #[cfg(target_os = "windows")]
     {
@@ -71,34 +349,75 @@ fn main() -> ExitCode {
         }
     };
 
-    let runtime_root = match find_runtime_root(exe_dir) {
-        Some(p) => p,
-        None => {
-            eprintln!(
-                "local-runtime-gateway: runtime not found. Looked for resources/local-runtime-python near: {}",
-                exe_dir.display()
-            );
-            return ExitCode::from(2);
-        }
-    };
+    let (python, pylibs) = match runtime_root_override().or_else(|| find_runtime_root(exe_dir)) {
+        Some(runtime_root) => {
+            let lock = match acquire_runtime_lock(&runtime_root) {
+                Ok(lock) => lock,
+                Err(message) => {
+                    eprintln!("local-runtime-gateway: {message}");
+                    return ExitCode::from(6);
+                }
+            };
 
-    let python = match find_python(&runtime_root) {
-        Some(p) => p,
-        None => {
-            eprintln!(
-                "local-runtime-gateway: embedded python not found under: {}",
-                runtime_root.display()
-            );
-            return ExitCode::from(3);
+            let python = match python_override().or_else(|| find_python(&runtime_root)) {
+                Some(p) => p,
+                None => {
+                    eprintln!(
+                        "local-runtime-gateway: embedded python not found under: {} (set LOCAL_RUNTIME_PYTHON to override)",
+                        runtime_root.display()
+                    );
+                    return ExitCode::from(3);
+                }
+            };
+
+            let pylibs = runtime_root.join("pylibs");
+            if !pylibs.exists() {
+                eprintln!(
+                    "local-runtime-gateway: pylibs not found: {} (set LOCAL_RUNTIME_DIR to override the runtime root)",
+                    pylibs.display()
+                );
+                return ExitCode::from(4);
+            }
+
+            if let Err(message) = verify_runtime_manifest(&runtime_root, &python) {
+                eprintln!("local-runtime-gateway: {message}");
+                return ExitCode::from(7);
+            }
+
+            // The tree is confirmed complete; release the lock before
+            // spawning python so a long-running gateway doesn't hold it.
+            drop(lock);
+            (python, Some(pylibs))
         }
+        None => match python_override() {
+            Some(python) => {
+                eprintln!(
+                    "local-runtime-gateway: embedded runtime not found near {}; using LOCAL_RUNTIME_PYTHON override {}",
+                    exe_dir.display(),
+                    python.display()
+                );
+                (python, None)
+            }
+            None => match find_system_python() {
+                Some(python) => {
+                    eprintln!(
+                        "local-runtime-gateway: embedded runtime not found near {}; falling back to system interpreter {}",
+                        exe_dir.display(),
+                        python.display()
+                    );
+                    (python, None)
+                }
+                None => {
+                    eprintln!(
+                        "local-runtime-gateway: runtime not found. Looked for resources/local-runtime-python near: {} (set LOCAL_RUNTIME_DIR to point at a runtime root, or LOCAL_RUNTIME_PYTHON to use a specific interpreter directly)",
+                        exe_dir.display()
+                    );
+                    return ExitCode::from(2);
+                }
+            },
+        },
     };
 
-    let pylibs = runtime_root.join("pylibs");
-    if !pylibs.exists() {
-        eprintln!("local-runtime-gateway: pylibs not found: {}", pylibs.display());
-        return ExitCode::from(4);
-    }
-
     let mut cmd = Command::new(python);
     cmd.arg("-m").arg("local_runtime.main");
 
@@ -107,21 +426,81 @@ fn main() -> ExitCode {
     }
 
     cmd.env("PYTHONNOUSERSITE", "1");
-    cmd.env("PYTHONPATH", &pylibs);
-    cmd.stdin(Stdio::null());
+    if let Some(pylibs) = pylibs {
+        cmd.env("PYTHONPATH", &pylibs);
+    }
+    cmd.stdin(Stdio::inherit());
     cmd.stdout(Stdio::inherit());
     cmd.stderr(Stdio::inherit());
 
-    let status = match cmd.status() {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("local-runtime-gateway: failed to start python: {e}");
-            return ExitCode::from(5);
+    exec_or_status(cmd)
+}
+
+/// On Unix, replaces this process with `cmd` via `exec` so the Python
+/// gateway inherits the controlling terminal and signal delivery directly
+/// instead of running behind a wrapper process that `cmd.status()` would
+/// leave in the tree. `exec` only returns on failure. Windows has no
+/// equivalent syscall, so it falls back to spawn-and-wait there.
+fn exec_or_status(mut cmd: Command) -> ExitCode {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = cmd.exec();
+        eprintln!("local-runtime-gateway: failed to exec python: {err}");
+        ExitCode::from(5)
+    }
+    #[cfg(not(unix))]
+    {
+        let status = match cmd.status() {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("local-runtime-gateway: failed to start python: {e}");
+                return ExitCode::from(5);
+            }
+        };
+        match status.code() {
+            Some(code) if code >= 0 => ExitCode::from(code as u8),
+            _ => ExitCode::from(1),
         }
-    };
+    }
+}
+
+#[cfg(test)]
+mod python_search_order_tests {
+    use super::*;
+
+    #[test]
+    fn prefers_plain_python_over_python3_across_all_dirs() {
+        let dirs = vec![PathBuf::from("/usr/local/bin"), PathBuf::from("/usr/bin")];
+        let order = python_search_order(&dirs);
+        let python_positions: Vec<usize> = order
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.file_stem().map(|s| s == "python").unwrap_or(false))
+            .map(|(i, _)| i)
+            .collect();
+        let python3_positions: Vec<usize> = order
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.file_stem().map(|s| s == "python3").unwrap_or(false))
+            .map(|(i, _)| i)
+            .collect();
+        assert!(python_positions.iter().max() < python3_positions.iter().min());
+    }
+
+    #[test]
+    fn prefers_python3_over_python2() {
+        let dirs = vec![PathBuf::from("/usr/bin")];
+        let order = python_search_order(&dirs);
+        let python3_index = order.iter().position(|p| p.file_stem().unwrap() == "python3").unwrap();
+        let python2_index = order.iter().position(|p| p.file_stem().unwrap() == "python2").unwrap();
+        assert!(python3_index < python2_index);
+    }
 
-    match status.code() {
-        Some(code) if code >= 0 => ExitCode::from(code as u8),
-        _ => ExitCode::from(1),
+    #[test]
+    fn searches_every_path_dir_for_each_name() {
+        let dirs = vec![PathBuf::from("/a"), PathBuf::from("/b"), PathBuf::from("/c")];
+        let order = python_search_order(&dirs);
+        assert_eq!(order.len(), dirs.len() * 3);
     }
 }
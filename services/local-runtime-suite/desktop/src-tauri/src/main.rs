@@ -1,27 +1,94 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use native_tls::{TlsConnector, TlsStream};
+use rand::rngs::OsRng;
+use rand::RngCore;
 use serde::Serialize;
 use serde_json::json;
 use std::collections::{HashMap, VecDeque};
 use std::ffi::OsString;
 use std::fs::OpenOptions;
-use std::io::{BufWriter, Read, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::net::{TcpListener, TcpStream};
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::{
+    atomic::{AtomicBool, Ordering},
     mpsc::{self, Sender},
     Arc, Mutex,
 };
 use std::thread;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::{Manager, RunEvent, WindowEvent};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{Emitter, Manager, RunEvent, WindowEvent};
 use tauri_plugin_shell::{process::CommandEvent, ShellExt};
+use tempfile::NamedTempFile;
 
 const MAX_LOG_LINES: usize = 500;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "trace" => Some(LogLevel::Trace),
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "error" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+
+    fn sniff(message: &str) -> Self {
+        let upper = message.to_ascii_uppercase();
+        if upper.contains("ERROR") || upper.contains("TRACEBACK") {
+            LogLevel::Error
+        } else if upper.contains("WARN") {
+            LogLevel::Warn
+        } else if upper.contains("DEBUG") {
+            LogLevel::Debug
+        } else {
+            LogLevel::Info
+        }
+    }
+}
+
+#[derive(Clone, Serialize, serde::Deserialize)]
+struct LogEvent {
+    ts: u64,
+    level: LogLevel,
+    source: String,
+    message: String,
+    fields: HashMap<String, String>,
+}
+
+impl LogEvent {
+    fn new(source: impl Into<String>, message: impl Into<String>) -> Self {
+        let message = message.into();
+        let level = LogLevel::sniff(&message);
+        Self {
+            ts: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|dur| dur.as_millis() as u64)
+                .unwrap_or(0),
+            level,
+            source: source.into(),
+            message,
+            fields: HashMap::new(),
+        }
+    }
+}
+
 #[derive(Clone)]
 struct LogSink {
     sender: Sender<LogCommand>,
@@ -29,7 +96,7 @@ struct LogSink {
 
 enum LogCommand {
     SetTarget(PathBuf),
-    Append(String),
+    Append(LogEvent),
 }
 
 impl LogSink {
@@ -57,8 +124,10 @@ impl LogSink {
                             }
                         }
                     }
-                    LogCommand::Append(line) => {
+                    LogCommand::Append(event) => {
                         if let Some(target) = writer.as_mut() {
+                            let line = serde_json::to_string(&event)
+                                .unwrap_or_else(|_| event.message.clone());
                             if writeln!(target, "{}", line).is_err() {
                                 writer = None;
                             } else {
@@ -76,8 +145,8 @@ impl LogSink {
         let _ = self.sender.send(LogCommand::SetTarget(path));
     }
 
-    fn append(&self, line: String) {
-        let _ = self.sender.send(LogCommand::Append(line));
+    fn append(&self, event: LogEvent) {
+        let _ = self.sender.send(LogCommand::Append(event));
     }
 }
 
@@ -86,6 +155,11 @@ struct ConfigPayload {
     port: u16,
     default_models: HashMap<String, String>,
     prefer_local: bool,
+    container_image: Option<String>,
+    share_enabled: bool,
+    daemon_mode: bool,
+    /// A `tls://host:port` relay address; see `parse_relay_target`.
+    tunnel_relay_url: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -100,7 +174,31 @@ enum GatewayError {
 #[derive(Default)]
 struct GatewayState {
     child: Option<GatewayChild>,
-    logs: VecDeque<String>,
+    logs: VecDeque<LogEvent>,
+    daemon: bool,
+    last_shutdown: Option<ShutdownKind>,
+    /// Set before `stop()` tears down the child so the supervisor thread
+    /// that notices the exit can tell a user-requested stop apart from a
+    /// crash and skip the auto-restart.
+    shutting_down: bool,
+    /// Set once the crash-loop breaker trips; cleared on the next
+    /// user-initiated `start()`.
+    crashed: bool,
+    /// Restarts observed within the current `CRASH_LOOP_WINDOW`.
+    restart_count: u32,
+    restart_window_started: Option<Instant>,
+    /// When the current child was (re)spawned; used to reset the backoff
+    /// once it has stayed up for `RESTART_STABLE_WINDOW`.
+    healthy_since: Option<Instant>,
+}
+
+/// Whether the most recent `stop()` let the gateway exit on its own within
+/// the grace period, or had to escalate to a hard kill.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ShutdownKind {
+    Graceful,
+    Forced,
 }
 
 #[derive(Clone)]
@@ -108,6 +206,9 @@ struct GatewayManager {
     inner: Arc<Mutex<GatewayState>>,
     log_sink: LogSink,
     log_dir: Arc<Mutex<Option<PathBuf>>>,
+    share_token: Arc<Mutex<Option<String>>>,
+    app_handle: Arc<Mutex<Option<tauri::AppHandle>>>,
+    tunnel: Arc<Mutex<TunnelState>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -124,23 +225,55 @@ struct GatewayErrorDetails {
 enum GatewayLaunchMode {
     Sidecar,
     Python,
+    Docker,
 }
 
 enum GatewayChild {
     Sidecar(tauri_plugin_shell::process::CommandChild),
     Python(Child),
+    Container {
+        runtime: String,
+        container_name: String,
+    },
+    /// A process we did not spawn ourselves this run but recognized via the
+    /// handshake file left behind by a previous launch.
+    Adopted { pid: u32 },
+}
+
+#[derive(Serialize, serde::Deserialize, Clone)]
+struct GatewayHandshake {
+    pid: Option<u32>,
+    container_runtime: Option<String>,
+    container_name: Option<String>,
+    port: u16,
+    mode: String,
+    share_token: Option<String>,
+    started_at: u64,
+    build_version: String,
+    daemon: bool,
 }
 
 #[derive(Clone)]
 struct GatewayLaunchConfig {
     mode: GatewayLaunchMode,
     port: u16,
+    /// The port from the user's saved config, before `resolve_effective_port`
+    /// may have bumped `port` to the next free candidate. Diagnostics that
+    /// need to know what the user actually asked for (e.g. "is something
+    /// squatting on my configured port?") should check this field, not
+    /// `port`, which may already have been moved off a busy configured port.
+    configured_port: u16,
     python_path: Option<String>,
     gateway_root: Option<PathBuf>,
     runtime_bin: Option<PathBuf>,
     config_path: PathBuf,
     args: Vec<String>,
     build_version: String,
+    container_runtime: Option<String>,
+    container_image: Option<String>,
+    share_enabled: bool,
+    share_token: Option<String>,
+    daemon: bool,
 }
 
 #[derive(Serialize)]
@@ -150,6 +283,9 @@ struct GatewayConnectionInfo {
     llm_url: String,
     stt_url: String,
     endpoints: GatewayEndpointExamples,
+    lan_url: Option<String>,
+    share_token: Option<String>,
+    pairing: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -163,6 +299,10 @@ struct GatewayEndpointExamples {
 struct StatusResponse {
     status: String,
     managed: bool,
+    capabilities: Vec<String>,
+    incompatibility: Option<String>,
+    tunnel_status: Option<String>,
+    tunnel_access_code: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -172,7 +312,7 @@ struct ModelsResponse {
 
 #[derive(Serialize)]
 struct LogsResponse {
-    logs: Vec<String>,
+    logs: Vec<LogEvent>,
 }
 
 #[derive(Serialize)]
@@ -180,11 +320,21 @@ struct DoctorResponse {
     checks: Vec<serde_json::Value>,
 }
 
+#[derive(Serialize)]
+struct DiagnosticsResponse {
+    bundle_path: String,
+    zipped: bool,
+}
+
 #[derive(serde::Deserialize)]
 struct GatewayConfigFile {
     port: Option<u16>,
     default_models: Option<HashMap<String, String>>,
     prefer_local: Option<bool>,
+    container_image: Option<String>,
+    share_enabled: Option<bool>,
+    daemon_mode: Option<bool>,
+    tunnel_relay_url: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -192,6 +342,33 @@ struct GatewayConfigResponse {
     port: u16,
     default_models: HashMap<String, String>,
     prefer_local: bool,
+    container_image: Option<String>,
+    share_enabled: bool,
+    daemon_mode: bool,
+    tunnel_relay_url: Option<String>,
+}
+
+/// Snapshot of the opt-in remote tunnel: whether a relay session is
+/// configured, its connection state, and the short-lived access code a
+/// second device needs to authenticate through the relay.
+#[derive(Serialize)]
+struct TunnelInfoResponse {
+    enabled: bool,
+    relay_url: Option<String>,
+    access_code: Option<String>,
+    status: String,
+}
+
+#[derive(Default)]
+struct TunnelState {
+    relay_url: Option<String>,
+    access_code: Option<String>,
+    stopped: Option<Arc<AtomicBool>>,
+    /// Clone of the raw TCP socket underneath the active TLS session, kept
+    /// only so `stop_tunnel` can force a shutdown of the connection; actual
+    /// traffic always goes through the TLS-wrapped stream in
+    /// `run_tunnel_loop`, never through this clone directly.
+    active_stream: Option<TcpStream>,
 }
 
 impl GatewayManager {
@@ -200,15 +377,229 @@ impl GatewayManager {
             inner: Arc::new(Mutex::new(GatewayState::default())),
             log_sink: LogSink::new(),
             log_dir: Arc::new(Mutex::new(None)),
+            share_token: Arc::new(Mutex::new(None)),
+            app_handle: Arc::new(Mutex::new(None)),
+            tunnel: Arc::new(Mutex::new(TunnelState::default())),
+        }
+    }
+
+    fn current_share_token(&self) -> Option<String> {
+        self.share_token.lock().ok().and_then(|guard| guard.clone())
+    }
+
+    fn set_share_token(&self, token: Option<String>) {
+        if let Ok(mut guard) = self.share_token.lock() {
+            *guard = token;
+        }
+    }
+
+    fn tunnel_info(&self) -> TunnelInfoResponse {
+        let guard = self.tunnel.lock().expect("tunnel lock");
+        let status = if guard.relay_url.is_none() {
+            "stopped"
+        } else if guard.active_stream.is_some() {
+            "connected"
+        } else {
+            "connecting"
+        };
+        TunnelInfoResponse {
+            enabled: guard.relay_url.is_some(),
+            relay_url: guard.relay_url.clone(),
+            access_code: guard.access_code.clone(),
+            status: status.to_string(),
+        }
+    }
+
+    /// Fills in a `StatusResponse`, stamping the current tunnel state onto
+    /// every construction site so `gateway_status` always reflects whether a
+    /// remote tunnel is live alongside the gateway itself.
+    fn with_tunnel_fields(
+        &self,
+        status: impl Into<String>,
+        managed: bool,
+        capabilities: Vec<String>,
+        incompatibility: Option<String>,
+    ) -> StatusResponse {
+        let tunnel = self.tunnel_info();
+        StatusResponse {
+            status: status.into(),
+            managed,
+            capabilities,
+            incompatibility,
+            tunnel_status: Some(tunnel.status),
+            tunnel_access_code: tunnel.access_code,
+        }
+    }
+
+    /// Opens (or restarts) a relay tunnel session, forwarding bytes between
+    /// the relay and the local gateway port on a background thread. Tied to
+    /// the gateway's lifecycle: `stop()` calls `stop_tunnel()` so a window
+    /// close or manual stop always tears the tunnel down too.
+    fn start_tunnel(&self, relay_url: String, port: u16) -> TunnelInfoResponse {
+        self.stop_tunnel();
+        let access_code = generate_bearer_token();
+        let stopped = Arc::new(AtomicBool::new(false));
+        {
+            let mut guard = self.tunnel.lock().expect("tunnel lock");
+            guard.relay_url = Some(relay_url.clone());
+            guard.access_code = Some(access_code.clone());
+            guard.stopped = Some(stopped.clone());
+            guard.active_stream = None;
+        }
+        self.push_log(format!("tunnel: starting relay session via {relay_url}"));
+        let manager = self.clone();
+        thread::spawn(move || run_tunnel_loop(manager, relay_url, port, access_code, stopped));
+        self.tunnel_info()
+    }
+
+    fn stop_tunnel(&self) {
+        let mut guard = self.tunnel.lock().expect("tunnel lock");
+        if let Some(stopped) = guard.stopped.take() {
+            stopped.store(true, Ordering::SeqCst);
+        }
+        if let Some(stream) = guard.active_stream.take() {
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+        }
+        let was_enabled = guard.relay_url.take().is_some();
+        guard.access_code = None;
+        drop(guard);
+        if was_enabled {
+            self.push_log("tunnel: stopped".to_string());
         }
     }
 
+    fn last_shutdown_kind(&self) -> Option<ShutdownKind> {
+        self.inner.lock().ok().and_then(|guard| guard.last_shutdown)
+    }
+
+    fn is_crashed(&self) -> bool {
+        self.inner.lock().map(|guard| guard.crashed).unwrap_or(false)
+    }
+
     fn initialize(&self, app: &tauri::AppHandle) {
+        if let Ok(mut guard) = self.app_handle.lock() {
+            *guard = Some(app.clone());
+        }
         if let Ok(dir) = app.path().app_log_dir() {
             self.configure_log_dir(dir);
         } else if let Ok(config_dir) = app.path().app_config_dir() {
             self.configure_log_dir(config_dir.join("logs"));
         }
+        self.try_adopt(app);
+    }
+
+    fn handshake_path(&self) -> Option<PathBuf> {
+        self.log_directory().map(|dir| dir.join("gateway.handshake.json"))
+    }
+
+    fn write_handshake(&self, handshake: &GatewayHandshake) {
+        let Some(path) = self.handshake_path() else {
+            return;
+        };
+        if let Ok(data) = serde_json::to_vec_pretty(handshake) {
+            let _ = std::fs::write(path, data);
+        }
+    }
+
+    fn read_handshake(&self) -> Option<GatewayHandshake> {
+        let path = self.handshake_path()?;
+        let data = std::fs::read(path).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    fn clear_handshake(&self) {
+        if let Some(path) = self.handshake_path() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Re-attach to a gateway left running by a previous launch of the app
+    /// (crash, force-quit, or a prior daemon-mode stop) so `stop()`/`status()`
+    /// keep working across restarts instead of orphaning the process. Also
+    /// re-engages the crash supervisor, otherwise an adopted gateway that
+    /// dies after this restart would never auto-restart or trip the
+    /// crash-loop breaker.
+    ///
+    /// Before adopting, checks the candidate's `/version` against this
+    /// launcher's build/protocol the same way `status_with_health` does —
+    /// an old gateway left behind by a previous install could otherwise be
+    /// adopted and supervised even though it's incompatible with the code
+    /// now running it. A mismatch reaps it instead of adopting it.
+    fn try_adopt(&self, app: &tauri::AppHandle) {
+        let Some(handshake) = self.read_handshake() else {
+            return;
+        };
+
+        let alive = match (&handshake.pid, &handshake.container_runtime, &handshake.container_name) {
+            (Some(pid), _, _) => is_pid_alive(*pid),
+            (None, Some(runtime), Some(name)) => container_is_running(runtime, name),
+            _ => false,
+        };
+        if !alive {
+            self.clear_handshake();
+            return;
+        }
+
+        if http_get_localhost(handshake.port, "/health").is_err() {
+            self.reap_handshake(&handshake);
+            return;
+        }
+
+        let build_version = app.package_info().version.to_string();
+        if let Some(version) = fetch_gateway_version(handshake.port) {
+            let protocol = version.protocol.unwrap_or(MIN_SUPPORTED_PROTOCOL);
+            let build_mismatch = version
+                .build_version
+                .as_deref()
+                .is_some_and(|gateway_build| gateway_build != build_version);
+            if protocol < MIN_SUPPORTED_PROTOCOL || build_mismatch {
+                self.push_notice(
+                    "found a gateway left over from a previous install that is too old to adopt; reaping it".to_string(),
+                );
+                self.reap_handshake(&handshake);
+                return;
+            }
+        }
+
+        let child = match (&handshake.pid, &handshake.container_runtime, &handshake.container_name) {
+            (Some(pid), _, _) => Some(GatewayChild::Adopted { pid: *pid }),
+            (None, Some(runtime), Some(name)) => Some(GatewayChild::Container {
+                runtime: runtime.clone(),
+                container_name: name.clone(),
+            }),
+            _ => None,
+        };
+        let Some(child) = child else {
+            self.clear_handshake();
+            return;
+        };
+
+        let mut guard = self.inner.lock().expect("state lock");
+        guard.child = Some(child);
+        guard.daemon = handshake.daemon;
+        drop(guard);
+        self.set_share_token(handshake.share_token.clone());
+        self.push_log(format!(
+            "launcher: adopted an existing gateway (mode {}, port {})",
+            handshake.mode, handshake.port
+        ));
+        self.supervise(app.clone());
+    }
+
+    /// Kills a recorded gateway that `try_adopt` will not adopt: either it is
+    /// alive but not answering health checks on the port we recorded for it
+    /// (most likely a genuine orphan), or it answers but is too old to adopt.
+    /// Either way it is killed rather than left to squat on the port forever.
+    fn reap_handshake(&self, handshake: &GatewayHandshake) {
+        if let Some(pid) = handshake.pid {
+            kill_pid(pid);
+        } else if let (Some(runtime), Some(name)) =
+            (&handshake.container_runtime, &handshake.container_name)
+        {
+            let _ = Command::new(runtime).args(["kill", name]).output();
+        }
+        self.clear_handshake();
+        self.push_notice("reaped an orphaned gateway process from a previous launch".to_string());
     }
 
     fn configure_log_dir(&self, dir: PathBuf) {
@@ -224,14 +615,22 @@ impl GatewayManager {
     }
 
     fn push_log(&self, line: impl Into<String>) {
-        let message = line.into();
+        self.push_event(LogEvent::new("launcher", line));
+    }
+
+    fn push_event(&self, event: LogEvent) {
         let mut guard = self.inner.lock().expect("state lock");
-        guard.logs.push_back(message.clone());
+        guard.logs.push_back(event.clone());
         if guard.logs.len() > MAX_LOG_LINES {
             guard.logs.pop_front();
         }
         drop(guard);
-        self.log_sink.append(message);
+        self.log_sink.append(event.clone());
+        if let Ok(guard) = self.app_handle.lock() {
+            if let Some(app) = guard.as_ref() {
+                let _ = app.emit("gateway://log", &event);
+            }
+        }
     }
 
     fn refresh_child_state(guard: &mut GatewayState) {
@@ -243,76 +642,140 @@ impl GatewayManager {
                     }
                 }
                 GatewayChild::Sidecar(_) => {}
+                GatewayChild::Container {
+                    runtime,
+                    container_name,
+                } => {
+                    if !container_is_running(runtime, container_name) {
+                        guard.child = None;
+                    }
+                }
+                GatewayChild::Adopted { pid } => {
+                    if !is_pid_alive(*pid) {
+                        guard.child = None;
+                    }
+                }
             }
         }
     }
 
     fn stop(&self) -> Result<StatusResponse, GatewayError> {
+        self.stop_tunnel();
         let mut guard = self.inner.lock().expect("state lock");
+        guard.shutting_down = true;
+        if guard.daemon && guard.child.is_some() {
+            guard.child = None;
+            guard.daemon = false;
+            drop(guard);
+            self.push_log(
+                "Gateway left running in daemon mode; it will be re-adopted on next launch",
+            );
+            self.set_share_token(None);
+            return Ok(self.with_tunnel_fields("running", false, Vec::new(), None));
+        }
         let child = guard.child.take();
         drop(guard);
 
         if let Some(child) = child {
-            match child {
-                GatewayChild::Python(mut child) => {
-                    let _ = child.kill();
-                    let _ = child.wait();
-                }
-                GatewayChild::Sidecar(child) => {
-                    let _ = child.kill();
-                }
+            let grace = shutdown_grace_period();
+            let kind = if terminate_child(child, grace) {
+                ShutdownKind::Graceful
+            } else {
+                ShutdownKind::Forced
+            };
+            if let Ok(mut guard) = self.inner.lock() {
+                guard.last_shutdown = Some(kind);
             }
-            self.push_log("Gateway stopped");
+            self.clear_handshake();
+            self.push_log(match kind {
+                ShutdownKind::Graceful => "Gateway stopped gracefully".to_string(),
+                ShutdownKind::Forced => format!(
+                    "Gateway did not exit within the {}s grace period; force-killed",
+                    grace.as_secs()
+                ),
+            });
         }
+        self.set_share_token(None);
 
-        Ok(StatusResponse {
-            status: "stopped".into(),
-            managed: false,
-        })
+        Ok(self.with_tunnel_fields("stopped", false, Vec::new(), None))
     }
 
     fn status(&self) -> StatusResponse {
         let mut guard = self.inner.lock().expect("state lock");
         Self::refresh_child_state(&mut guard);
         let managed = guard.child.is_some();
-        let status = if managed { "running" } else { "stopped" };
-        StatusResponse {
-            status: status.into(),
-            managed,
-        }
+        let status = if managed {
+            "running"
+        } else if guard.crashed {
+            "crashed"
+        } else {
+            "stopped"
+        };
+        drop(guard);
+        self.with_tunnel_fields(status, managed, Vec::new(), None)
     }
 
-    fn status_with_health(&self, port: u16) -> StatusResponse {
+    /// Checks the live gateway for a `/version` or `/health` payload and
+    /// compares its advertised build and protocol against what this
+    /// launcher expects. A gateway that is reachable but too old (or from
+    /// a mismatched build) is reported as `"incompatible"` rather than
+    /// `"running"` so the frontend can surface a concrete fix instead of
+    /// treating a stale gateway as healthy.
+    fn status_with_health(&self, port: u16, build_version: &str) -> StatusResponse {
         let mut guard = self.inner.lock().expect("state lock");
         Self::refresh_child_state(&mut guard);
-        if guard.child.is_some() {
-            let managed = guard.child.is_some();
-            return StatusResponse {
-                status: "running".into(),
-                managed,
-            };
-        }
+        let managed = guard.child.is_some();
+        let crashed = guard.crashed;
         drop(guard);
 
-        if http_get_localhost(port, "/health").is_ok() {
-            StatusResponse {
-                status: "running".into(),
-                managed: false,
-            }
-        } else {
-            StatusResponse {
-                status: "stopped".into(),
-                managed: false,
+        let running = managed || http_get_localhost(port, "/health").is_ok();
+        if !running {
+            let status = if crashed { "crashed" } else { "stopped" };
+            return self.with_tunnel_fields(status, false, Vec::new(), None);
+        }
+
+        match fetch_gateway_version(port) {
+            Some(version) => {
+                let protocol = version.protocol.unwrap_or(MIN_SUPPORTED_PROTOCOL);
+                if protocol < MIN_SUPPORTED_PROTOCOL {
+                    return self.with_tunnel_fields(
+                        "incompatible",
+                        managed,
+                        version.capabilities,
+                        Some(format!(
+                            "Gateway protocol {} is older than the minimum supported protocol {}. Update the bundled runtime or reinstall the app.",
+                            protocol, MIN_SUPPORTED_PROTOCOL
+                        )),
+                    );
+                }
+                if let Some(gateway_build) = version.build_version.as_deref() {
+                    if gateway_build != build_version {
+                        return self.with_tunnel_fields(
+                            "incompatible",
+                            managed,
+                            version.capabilities,
+                            Some(format!(
+                                "Gateway build {gateway_build} does not match launcher build {build_version}. Restart the app to relaunch a matching gateway."
+                            )),
+                        );
+                    }
+                }
+                self.with_tunnel_fields("running", managed, version.capabilities, None)
             }
+            None => self.with_tunnel_fields("running", managed, Vec::new(), None),
         }
     }
 
-    fn logs(&self) -> LogsResponse {
+    fn logs(&self, min_level: Option<LogLevel>) -> LogsResponse {
         let mut guard = self.inner.lock().expect("state lock");
         Self::refresh_child_state(&mut guard);
-        LogsResponse {
-            logs: guard.logs.iter().cloned().collect(),
-        }
+        let logs = guard
+            .logs
+            .iter()
+            .filter(|event| min_level.map(|min| event.level >= min).unwrap_or(true))
+            .cloned()
+            .collect();
+        LogsResponse { logs }
     }
 
     fn push_notice(&self, message: impl Into<String>) {
@@ -324,11 +787,40 @@ impl GatewayManager {
         guard.child = None;
     }
 
+    /// Joins the most recent `stderr`-sourced log lines, for attaching to a
+    /// `SpawnFailed` error when a child process spawns but never becomes
+    /// healthy.
+    fn stderr_tail(&self, max_lines: usize) -> String {
+        let guard = self.inner.lock().expect("state lock");
+        guard
+            .logs
+            .iter()
+            .filter(|event| event.source == "stderr")
+            .rev()
+            .take(max_lines)
+            .map(|event| event.message.clone())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     fn log_directory(&self) -> Option<PathBuf> {
         self.log_dir.lock().ok().and_then(|path| path.clone())
     }
 
-    fn write_report(&self, prefix: &str, payload: &serde_json::Value) -> Option<PathBuf> {
+    /// Writes `payload` as a pretty-printed JSON report under
+    /// `<log_dir>/reports`, redacting `secret` (the share token in force at
+    /// the time this *specific* report was generated, not whatever the live
+    /// token happens to be later) so a token never lands on disk even
+    /// transiently.
+    fn write_report(
+        &self,
+        prefix: &str,
+        payload: &serde_json::Value,
+        secret: Option<&str>,
+    ) -> Option<PathBuf> {
         let dir = self.log_directory()?;
         let reports_dir = dir.join("reports");
         if std::fs::create_dir_all(&reports_dir).is_err() {
@@ -340,7 +832,8 @@ impl GatewayManager {
             .unwrap_or(0);
         let filename = format!("{}-{}.json", prefix, timestamp);
         let path = reports_dir.join(filename);
-        let data = serde_json::to_vec_pretty(payload).ok()?;
+        let text = serde_json::to_string_pretty(payload).ok()?;
+        let data = scrub_secrets(&text, secret);
         if std::fs::write(&path, data).is_ok() {
             Some(path)
         } else {
@@ -367,7 +860,9 @@ impl GatewayManager {
                 "hint": &details.hint,
                 "details": payload,
             });
-            if let Some(path) = self.write_report("spawn-failure", &report) {
+            if let Some(path) =
+                self.write_report("spawn-failure", &report, context.share_token.as_deref())
+            {
                 self.push_notice(format!(
                     "launcher: spawn failure report saved to {}",
                     path.display()
@@ -393,20 +888,28 @@ fn stop_gateway(state: tauri::State<GatewayManager>) -> Result<StatusResponse, G
 #[tauri::command]
 fn gateway_status(app: tauri::AppHandle, state: tauri::State<GatewayManager>) -> StatusResponse {
     if let Ok(config) = build_launch_config(&app) {
-        state.status_with_health(config.port)
+        state.status_with_health(config.port, &config.build_version)
     } else {
         state.status()
     }
 }
 
 #[tauri::command]
-fn gateway_logs(state: tauri::State<GatewayManager>) -> LogsResponse {
-    state.logs()
+fn gateway_logs(state: tauri::State<GatewayManager>, min_level: Option<String>) -> LogsResponse {
+    let min_level = min_level.as_deref().and_then(LogLevel::parse);
+    state.logs(min_level)
 }
 
 #[tauri::command]
 fn gateway_doctor(app: tauri::AppHandle, state: tauri::State<GatewayManager>) -> DoctorResponse {
-    let config = match build_launch_config(&app) {
+    gateway_doctor_report(&app, &state)
+}
+
+/// Shared by the `gateway_doctor` command and the diagnostics bundle, so a
+/// bug report always carries the exact same checks a user would see in the
+/// app.
+fn gateway_doctor_report(app: &tauri::AppHandle, state: &GatewayManager) -> DoctorResponse {
+    let config = match build_launch_config(app) {
         Ok(config) => config,
         Err(error) => {
             let (details, hint) = describe_gateway_error(&error);
@@ -482,7 +985,7 @@ fn gateway_doctor(app: tauri::AppHandle, state: tauri::State<GatewayManager>) ->
                 }
             }
         }
-        GatewayLaunchMode::Sidecar => match resolve_sidecar_path(&app) {
+        GatewayLaunchMode::Sidecar => match resolve_sidecar_path(app) {
             Some(path) => {
                 checks.push(serde_json::json!( {
                     "title": "Gateway sidecar binary",
@@ -509,29 +1012,175 @@ fn gateway_doctor(app: tauri::AppHandle, state: tauri::State<GatewayManager>) ->
                     }));
             }
         },
+        GatewayLaunchMode::Docker => {
+            match &config.container_runtime {
+                Some(runtime) => {
+                    checks.push(serde_json::json!({
+                        "title": "Container runtime available",
+                        "status": "ok",
+                        "details": format!("Using {runtime}"),
+                        "fix": null
+                    }));
+
+                    let image = config.container_image.clone().unwrap_or_default();
+                    let image_present = Command::new(runtime)
+                        .args(["image", "inspect", &image])
+                        .output()
+                        .map(|output| output.status.success())
+                        .unwrap_or(false);
+                    if image_present {
+                        checks.push(serde_json::json!({
+                            "title": "Container image present",
+                            "status": "ok",
+                            "details": format!("{image} is present locally"),
+                            "fix": null
+                        }));
+                    } else {
+                        checks.push(serde_json::json!({
+                            "title": "Container image present",
+                            "status": "error",
+                            "details": format!("{image} is not present locally"),
+                            "fix": format!("Run `{runtime} pull {image}` to fetch it.")
+                        }));
+                    }
+
+                    let gpu_present = Path::new("/dev/nvidia0").exists()
+                        || Path::new("/dev/kfd").exists();
+                    checks.push(serde_json::json!({
+                        "title": "GPU device passthrough",
+                        "status": if gpu_present { "ok" } else { "warning" },
+                        "details": if gpu_present {
+                            "A GPU device node was detected on the host.".to_string()
+                        } else {
+                            "No GPU device node detected; the container will run on CPU.".to_string()
+                        },
+                        "fix": if gpu_present {
+                            None
+                        } else {
+                            Some("Pass `--gpus all` (docker) or the equivalent device flags if you expect GPU acceleration.".to_string())
+                        }
+                    }));
+                }
+                None => {
+                    checks.push(serde_json::json!({
+                        "title": "Container runtime available",
+                        "status": "error",
+                        "details": "No container runtime resolved for this launch config.",
+                        "fix": "Install Docker or Podman and ensure it is on PATH."
+                    }));
+                }
+            }
+        }
+    }
+
+    if config.share_enabled {
+        if state.current_share_token().is_some() {
+            checks.push(serde_json::json!({
+                "title": "Share mode token",
+                "status": "ok",
+                "details": "Share mode is on and a bearer token is set.",
+                "fix": null
+            }));
+        } else {
+            checks.push(serde_json::json!({
+                "title": "Share mode token",
+                "status": "error",
+                "details": "Share mode is enabled but no bearer token is set — the gateway is reachable on the LAN without authentication.",
+                "fix": "Restart the gateway so a token can be minted, or disable share mode."
+            }));
+        }
+    }
+
+    if let Some(kind) = state.last_shutdown_kind() {
+        checks.push(serde_json::json!({
+            "title": "Last shutdown",
+            "status": if kind == ShutdownKind::Graceful { "ok" } else { "warning" },
+            "details": match kind {
+                ShutdownKind::Graceful => "The gateway exited on its own the last time it was stopped.".to_string(),
+                ShutdownKind::Forced => "The gateway did not exit within the grace period and was force-killed the last time it was stopped.".to_string(),
+            },
+            "fix": if kind == ShutdownKind::Forced {
+                Some("Check the gateway logs for a hang during shutdown; increase LOCAL_RUNTIME_SHUTDOWN_GRACE_SECS if it just needs more time.".to_string())
+            } else {
+                None
+            }
+        }));
+    }
+
+    if state.is_crashed() {
+        checks.push(serde_json::json!({
+            "title": "Automatic restart",
+            "status": "error",
+            "details": format!(
+                "The gateway crashed repeatedly and automatic restarts were stopped after {} attempts within {}s.",
+                CRASH_LOOP_MAX_RESTARTS,
+                CRASH_LOOP_WINDOW.as_secs()
+            ),
+            "fix": "Check the gateway logs for the underlying error, then start the gateway again."
+        }));
     }
 
-    let status = state.status_with_health(config.port).status;
-    let port_in_use = TcpListener::bind(("127.0.0.1", config.port)).is_err();
-    if port_in_use && status == "running" {
+    let health = state.status_with_health(config.port, &config.build_version);
+    let status = health.status.clone();
+    if status == "incompatible" {
+        checks.push(serde_json::json!({
+            "title": "Gateway compatibility",
+            "status": "error",
+            "details": health.incompatibility.clone().unwrap_or_else(|| "The running gateway is incompatible with this launcher.".to_string()),
+            "fix": "Stop the gateway and restart the app so a compatible gateway is launched."
+        }));
+    } else if status == "running" && !health.capabilities.is_empty() {
+        checks.push(serde_json::json!({
+            "title": "Gateway compatibility",
+            "status": "ok",
+            "details": format!("Gateway advertises {} capabilit{}.", health.capabilities.len(), if health.capabilities.len() == 1 { "y" } else { "ies" }),
+            "fix": null
+        }));
+    }
+    // Checked against `configured_port` (what the user actually asked for),
+    // not `config.port` (possibly bumped to the next free candidate by
+    // `resolve_effective_port`) — otherwise a leftover process squatting on
+    // the configured port would never surface here, since the launcher
+    // would have silently hopped to a free port before this check ran.
+    let port_in_use = TcpListener::bind(("127.0.0.1", config.configured_port)).is_err();
+    if port_in_use
+        && config.port == config.configured_port
+        && (status == "running" || status == "incompatible")
+    {
         checks.push(serde_json::json!({
             "title": "Port availability",
             "status": "ok",
-            "details": format!("Port {} is bound by the running gateway.", config.port),
+            "details": format!("Port {} is bound by the running gateway.", config.configured_port),
             "fix": null
         }));
     } else if port_in_use {
+        let owner = find_port_owner(config.configured_port);
+        let details = match &owner {
+            Some(owner) => format!(
+                "Port {} is used by {} (pid {}) — possibly a previous gateway.",
+                config.configured_port, owner.process_name, owner.pid
+            ),
+            None => format!("Port {} is already in use.", config.configured_port),
+        };
+        let fix = match &owner {
+            Some(owner) => format!(
+                "Reclaim pid {} (see `gateway_reclaim_port`) or choose another port in the desktop app.",
+                owner.pid
+            ),
+            None => "Choose another port in the desktop app or stop the process using this port.".to_string(),
+        };
         checks.push(serde_json::json!({
             "title": "Port availability",
             "status": "error",
-            "details": format!("Port {} is already in use.", config.port),
-            "fix": "Choose another port in the desktop app or stop the process using this port."
+            "details": details,
+            "fix": fix,
+            "owner_pid": owner.as_ref().map(|owner| owner.pid),
         }));
     } else {
         checks.push(serde_json::json!({
             "title": "Port availability",
             "status": "ok",
-            "details": format!("Port {} is free.", config.port),
+            "details": format!("Port {} is free.", config.configured_port),
             "fix": null
         }));
     }
@@ -567,6 +1216,135 @@ fn gateway_doctor(app: tauri::AppHandle, state: tauri::State<GatewayManager>) ->
     DoctorResponse { checks }
 }
 
+/// Assembles a single redacted bundle (launcher log tail, doctor output,
+/// effective config, launch mode/args, OS/build info, and recent
+/// spawn-failure reports) that a user can attach to a bug report without
+/// having to go hunting through `reports/` themselves.
+#[tauri::command]
+fn gateway_collect_diagnostics(
+    app: tauri::AppHandle,
+    state: tauri::State<GatewayManager>,
+) -> Result<DiagnosticsResponse, GatewayError> {
+    let log_dir = state
+        .log_directory()
+        .ok_or_else(|| GatewayError::ConfigDir("Log directory is not initialized".into()))?;
+    let reports_dir = log_dir.join("reports");
+    std::fs::create_dir_all(&reports_dir).map_err(|err| GatewayError::Io(err.to_string()))?;
+
+    let bundle_dir = reports_dir.join(format!("diagnostics-{}", now_unix_secs()));
+    std::fs::create_dir_all(&bundle_dir).map_err(|err| GatewayError::Io(err.to_string()))?;
+
+    let share_token = state.current_share_token();
+    let scrub = |text: String| scrub_secrets(&text, share_token.as_deref());
+
+    let log_tail = std::fs::read_to_string(log_dir.join("launcher.log")).unwrap_or_default();
+    std::fs::write(bundle_dir.join("launcher.log"), scrub(tail_lines(&log_tail, 500)))
+        .map_err(|err| GatewayError::Io(err.to_string()))?;
+
+    let doctor = gateway_doctor_report(&app, &state);
+    let doctor_json = serde_json::to_string_pretty(&doctor).unwrap_or_default();
+    std::fs::write(bundle_dir.join("doctor.json"), scrub(doctor_json))
+        .map_err(|err| GatewayError::Io(err.to_string()))?;
+
+    let config = read_gateway_config(&app).ok();
+    let launch_config = build_launch_config(&app).ok();
+    let summary = json!({
+        "build_version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "effective_config": config,
+        "launch_mode": launch_config.as_ref().map(|c| format!("{:?}", c.mode)),
+        "launch_args": launch_config.as_ref().map(|c| c.args.clone()),
+        "launch_port": launch_config.as_ref().map(|c| c.port),
+    });
+    let summary_json = serde_json::to_string_pretty(&summary).unwrap_or_default();
+    std::fs::write(bundle_dir.join("summary.json"), scrub(summary_json))
+        .map_err(|err| GatewayError::Io(err.to_string()))?;
+
+    let mut spawn_failures: Vec<PathBuf> = std::fs::read_dir(&reports_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .map(|name| name.starts_with("spawn-failure-"))
+                        .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    spawn_failures.sort();
+    for path in spawn_failures.into_iter().rev().take(5) {
+        if let (Ok(contents), Some(name)) = (std::fs::read_to_string(&path), path.file_name()) {
+            let _ = std::fs::write(bundle_dir.join(name), scrub(contents));
+        }
+    }
+
+    match zip_bundle(&bundle_dir) {
+        Some(zip_path) => {
+            let _ = std::fs::remove_dir_all(&bundle_dir);
+            Ok(DiagnosticsResponse {
+                bundle_path: zip_path.to_string_lossy().to_string(),
+                zipped: true,
+            })
+        }
+        None => Ok(DiagnosticsResponse {
+            bundle_path: bundle_dir.to_string_lossy().to_string(),
+            zipped: false,
+        }),
+    }
+}
+
+fn tail_lines(text: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    lines[start..].join("\n")
+}
+
+fn scrub_secrets(text: &str, share_token: Option<&str>) -> String {
+    match share_token {
+        Some(token) if !token.is_empty() => text.replace(token, "***REDACTED***"),
+        _ => text.to_string(),
+    }
+}
+
+fn zip_bundle(bundle_dir: &Path) -> Option<PathBuf> {
+    let zip_path = bundle_dir.with_extension("zip");
+
+    #[cfg(target_os = "windows")]
+    let zipped = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "Compress-Archive -Path '{}\\*' -DestinationPath '{}' -Force",
+                bundle_dir.display(),
+                zip_path.display()
+            ),
+        ])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+
+    #[cfg(not(target_os = "windows"))]
+    let zipped = Command::new("zip")
+        .arg("-r")
+        .arg(&zip_path)
+        .arg(".")
+        .current_dir(bundle_dir)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+
+    if zipped {
+        Some(zip_path)
+    } else {
+        None
+    }
+}
+
 #[tauri::command]
 fn gateway_models(app: tauri::AppHandle, state: tauri::State<GatewayManager>) -> ModelsResponse {
     let config = match build_launch_config(&app) {
@@ -605,6 +1383,10 @@ fn save_gateway_config(app: tauri::AppHandle, payload: ConfigPayload) -> Result<
         "port": payload.port,
         "default_models": payload.default_models,
         "prefer_local": payload.prefer_local,
+        "container_image": payload.container_image,
+        "share_enabled": payload.share_enabled,
+        "daemon_mode": payload.daemon_mode,
+        "tunnel_relay_url": payload.tunnel_relay_url,
         "data_dir": target_dir.join("data").to_string_lossy(),
         "cache_dir": target_dir.join("cache").to_string_lossy()
     });
@@ -619,11 +1401,27 @@ fn gateway_config(app: tauri::AppHandle) -> Result<GatewayConfigResponse, Gatewa
 }
 
 #[tauri::command]
-fn gateway_connection_info(app: tauri::AppHandle) -> Result<GatewayConnectionInfo, GatewayError> {
+fn gateway_connection_info(
+    app: tauri::AppHandle,
+    state: tauri::State<GatewayManager>,
+) -> Result<GatewayConnectionInfo, GatewayError> {
     let config = read_gateway_config(&app)?;
-    let base_url = format!("http://127.0.0.1:{}", config.port);
+    let port = build_launch_config(&app)
+        .map(|launch| launch.port)
+        .unwrap_or(config.port);
+    let base_url = format!("http://127.0.0.1:{}", port);
+    let share_token = state.current_share_token();
+    let lan_url = if config.share_enabled {
+        local_lan_ip().map(|ip| format!("http://{}:{}", ip, port))
+    } else {
+        None
+    };
+    let pairing = match (&lan_url, &share_token) {
+        (Some(url), Some(token)) => Some(format!("{url}?token={token}")),
+        _ => None,
+    };
     Ok(GatewayConnectionInfo {
-        port: config.port,
+        port,
         base_url: base_url.clone(),
         llm_url: base_url.clone(),
         stt_url: base_url.clone(),
@@ -632,9 +1430,65 @@ fn gateway_connection_info(app: tauri::AppHandle) -> Result<GatewayConnectionInf
             llm_example: format!("{base_url}/v1/responses"),
             stt_example: format!("{base_url}/v1/audio/transcriptions"),
         },
+        lan_url,
+        share_token,
+        pairing,
     })
 }
 
+#[derive(serde::Deserialize)]
+struct TunnelStartPayload {
+    relay_url: Option<String>,
+}
+
+/// Starts (or restarts) the relay tunnel, using the explicit `relay_url`
+/// from the payload if given, otherwise falling back to the one saved via
+/// `save_gateway_config`.
+#[tauri::command]
+fn start_gateway_tunnel(
+    app: tauri::AppHandle,
+    state: tauri::State<GatewayManager>,
+    payload: Option<TunnelStartPayload>,
+) -> Result<TunnelInfoResponse, GatewayError> {
+    let config = read_gateway_config(&app)?;
+    let relay_url = payload
+        .and_then(|payload| payload.relay_url)
+        .or(config.tunnel_relay_url)
+        .ok_or_else(|| GatewayError::Config("No tunnel relay URL configured".into()))?;
+    let port = build_launch_config(&app)
+        .map(|launch| launch.port)
+        .unwrap_or(config.port);
+    Ok(state.start_tunnel(relay_url, port))
+}
+
+#[tauri::command]
+fn stop_gateway_tunnel(state: tauri::State<GatewayManager>) -> Result<TunnelInfoResponse, GatewayError> {
+    state.stop_tunnel();
+    Ok(state.tunnel_info())
+}
+
+#[tauri::command]
+fn gateway_tunnel_info(state: tauri::State<GatewayManager>) -> TunnelInfoResponse {
+    state.tunnel_info()
+}
+
+/// Generates a 32-char hex token from OS-backed randomness. Both the LAN
+/// share bearer token and the tunnel access code authenticate a remote
+/// peer against this process, so the bytes must be unguessable — not
+/// derived from a seed (process start time, pid) an attacker could narrow
+/// down.
+fn generate_bearer_token() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn local_lan_ip() -> Option<std::net::IpAddr> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
 fn default_python_binary() -> String {
     if cfg!(windows) {
         "python".to_string()
@@ -661,6 +1515,10 @@ fn read_gateway_config(app: &tauri::AppHandle) -> Result<GatewayConfigResponse,
             port: 8484,
             default_models: HashMap::new(),
             prefer_local: true,
+            container_image: None,
+            share_enabled: false,
+            daemon_mode: false,
+            tunnel_relay_url: None,
         });
     }
     let data = std::fs::read(&config_path).map_err(|err| GatewayError::Io(err.to_string()))?;
@@ -670,19 +1528,86 @@ fn read_gateway_config(app: &tauri::AppHandle) -> Result<GatewayConfigResponse,
         port: parsed.port.unwrap_or(8484),
         default_models: parsed.default_models.unwrap_or_default(),
         prefer_local: parsed.prefer_local.unwrap_or(true),
+        container_image: parsed.container_image,
+        share_enabled: parsed.share_enabled.unwrap_or(false),
+        daemon_mode: parsed.daemon_mode.unwrap_or(false),
+        tunnel_relay_url: parsed.tunnel_relay_url,
     })
 }
 
+fn data_dir_for(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .map(|dir| dir.join("data"))
+        .unwrap_or_else(|| PathBuf::from("data"))
+}
+
+fn cache_dir_for(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .map(|dir| dir.join("cache"))
+        .unwrap_or_else(|| PathBuf::from("cache"))
+}
+
+fn resolve_container_runtime() -> Option<String> {
+    if let Ok(explicit) = std::env::var("LOCAL_RUNTIME_CONTAINER_RUNTIME") {
+        if Command::new(&explicit).arg("--version").output().is_ok() {
+            return Some(explicit);
+        }
+        return None;
+    }
+    for candidate in ["docker", "podman"] {
+        if Command::new(candidate).arg("--version").output().is_ok() {
+            return Some(candidate.to_string());
+        }
+    }
+    None
+}
+
+fn resolve_container_image(config: &GatewayConfigResponse) -> Option<String> {
+    std::env::var("LOCAL_RUNTIME_IMAGE")
+        .ok()
+        .or_else(|| config.container_image.clone())
+}
+
+fn container_is_running(runtime: &str, container_name: &str) -> bool {
+    Command::new(runtime)
+        .args(["inspect", "-f", "{{.State.Running}}", container_name])
+        .output()
+        .map(|output| {
+            output.status.success()
+                && String::from_utf8_lossy(&output.stdout).trim() == "true"
+        })
+        .unwrap_or(false)
+}
+
 fn build_launch_config(app: &tauri::AppHandle) -> Result<GatewayLaunchConfig, GatewayError> {
-    let config = read_gateway_config(app)?;
+    let mut config = read_gateway_config(app)?;
+    let configured_port = config.port;
+    config.port = resolve_effective_port(config.port);
     let config_path = resolve_config_path(app)?;
     let build_version = app.package_info().version.to_string();
-    let base_args = vec![
+    let share_token = if config.share_enabled {
+        Some(generate_bearer_token())
+    } else {
+        None
+    };
+    let mut base_args = vec![
         "--port".to_string(),
         config.port.to_string(),
         "--config".to_string(),
         config_path.to_string_lossy().to_string(),
+        "--host".to_string(),
+        if config.share_enabled {
+            "0.0.0.0".to_string()
+        } else {
+            "127.0.0.1".to_string()
+        },
     ];
+    if let Some(token) = share_token.as_ref() {
+        base_args.push("--share-token".to_string());
+        base_args.push(token.clone());
+    }
     let forced_mode = std::env::var("LOCAL_RUNTIME_LAUNCH").ok();
     let prefer_sidecar = forced_mode
         .as_deref()
@@ -692,6 +1617,10 @@ fn build_launch_config(app: &tauri::AppHandle) -> Result<GatewayLaunchConfig, Ga
         .as_deref()
         .map(|mode| mode == "python")
         .unwrap_or(false);
+    let prefer_docker = forced_mode
+        .as_deref()
+        .map(|mode| mode == "docker")
+        .unwrap_or(false);
     let sidecar_available =
         resolve_sidecar_path(app).is_some() && resolve_sidecar_command(app).is_ok();
     let embedded_runtime = if prefer_sidecar {
@@ -707,12 +1636,18 @@ fn build_launch_config(app: &tauri::AppHandle) -> Result<GatewayLaunchConfig, Ga
     let make_sidecar_config = || GatewayLaunchConfig {
         mode: GatewayLaunchMode::Sidecar,
         port: config.port,
+        configured_port,
         python_path: None,
         gateway_root: None,
         runtime_bin: None,
         config_path: config_path.clone(),
         args: base_args.clone(),
         build_version: build_version.clone(),
+        container_runtime: None,
+        container_image: None,
+        share_enabled: config.share_enabled,
+        share_token: share_token.clone(),
+        daemon: config.daemon_mode,
     };
 
     let make_python_config = |python_path: &str,
@@ -726,12 +1661,76 @@ fn build_launch_config(app: &tauri::AppHandle) -> Result<GatewayLaunchConfig, Ga
         GatewayLaunchConfig {
             mode: GatewayLaunchMode::Python,
             port: config.port,
+            configured_port,
             python_path: Some(python_path.to_string()),
             gateway_root: Some(gateway_root.to_path_buf()),
             runtime_bin: runtime_bin.map(|path| path.to_path_buf()),
             config_path: config_path.clone(),
             args,
             build_version: build_version.clone(),
+            container_runtime: None,
+            container_image: None,
+            share_enabled: config.share_enabled,
+            share_token: share_token.clone(),
+            daemon: config.daemon_mode,
+        }
+    };
+
+    let make_docker_config = |runtime: &str, image: &str| {
+        let data_dir = data_dir_for(&config_path);
+        let cache_dir = cache_dir_for(&config_path);
+        let bind_host = if config.share_enabled { "0.0.0.0" } else { "127.0.0.1" };
+        // The host config file isn't otherwise visible inside the
+        // container, so it's bind-mounted read-only at a fixed path and
+        // `--config` below points at that path, not `config_path` (which
+        // only resolves on the host).
+        const CONTAINER_CONFIG_PATH: &str = "/config/gateway.json";
+        let mut args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "--name".to_string(),
+            format!("local-runtime-gateway-{}", config.port),
+            "-p".to_string(),
+            format!("{}:{1}:{1}", bind_host, config.port),
+            "-v".to_string(),
+            format!("{}:/data", data_dir.display()),
+            "-v".to_string(),
+            format!("{}:/cache", cache_dir.display()),
+            "-v".to_string(),
+            format!("{}:{}:ro", config_path.display(), CONTAINER_CONFIG_PATH),
+        ];
+        if let Some(token) = share_token.as_ref() {
+            args.push("-e".to_string());
+            args.push(format!("LOCAL_RUNTIME_SHARE_TOKEN={}", token));
+        }
+        args.push(image.to_string());
+        // Trailing args after the image name become the container's
+        // command, mirroring the `--port`/`--config`/`--host` flags passed
+        // to the sidecar/python launchers above — without these the
+        // container keeps listening on whatever port it hardcodes
+        // internally, which silently diverges the moment
+        // `resolve_effective_port` bumps `config.port` off its default.
+        args.push("--port".to_string());
+        args.push(config.port.to_string());
+        args.push("--config".to_string());
+        args.push(CONTAINER_CONFIG_PATH.to_string());
+        args.push("--host".to_string());
+        args.push(bind_host.to_string());
+        GatewayLaunchConfig {
+            mode: GatewayLaunchMode::Docker,
+            port: config.port,
+            configured_port,
+            python_path: None,
+            gateway_root: None,
+            runtime_bin: None,
+            config_path: config_path.clone(),
+            args,
+            build_version: build_version.clone(),
+            container_runtime: Some(runtime.to_string()),
+            container_image: Some(image.to_string()),
+            share_enabled: config.share_enabled,
+            share_token: share_token.clone(),
+            daemon: config.daemon_mode,
         }
     };
 
@@ -744,6 +1743,22 @@ fn build_launch_config(app: &tauri::AppHandle) -> Result<GatewayLaunchConfig, Ga
         )
     };
 
+    if prefer_docker {
+        let runtime = resolve_container_runtime().ok_or_else(|| {
+            GatewayError::Config(
+                "No container runtime found; install Docker/Podman or unset LOCAL_RUNTIME_LAUNCH=docker."
+                    .into(),
+            )
+        })?;
+        let image = resolve_container_image(&config).ok_or_else(|| {
+            GatewayError::Config(
+                "No container image configured; set LOCAL_RUNTIME_IMAGE or container_image in the gateway config."
+                    .into(),
+            )
+        })?;
+        return Ok(make_docker_config(&runtime, &image));
+    }
+
     if prefer_sidecar {
         if !sidecar_available {
             return Err(GatewayError::Config(
@@ -1041,35 +2056,683 @@ fn run_python_import_check(config: &GatewayLaunchConfig) -> Result<String, Gatew
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
+struct PortOwner {
+    pid: u32,
+    process_name: String,
+}
+
+/// Finds the socket inode listening on `port` in the `/proc/net/tcp` table
+/// format (header line followed by whitespace-separated fields, local
+/// address as `hex_addr:hex_port`, inode in the 10th field). Split out from
+/// `find_port_owner` so the table-parsing logic can be tested without
+/// needing a real `/proc/net/tcp`.
+#[cfg(target_os = "linux")]
+fn find_listening_inode(table: &str, port: u16) -> Option<String> {
+    let hex_port = format!("{:04X}", port);
+    table.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let local_address = fields.nth(1)?;
+        // Skip rem_address; `0A` is TCP_LISTEN.
+        let state = fields.nth(1)?;
+        let (_, port_hex) = local_address.split_once(':')?;
+        if state == "0A" && port_hex.eq_ignore_ascii_case(&hex_port) {
+            fields.nth(5).map(|inode| inode.to_string())
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn find_port_owner(port: u16) -> Option<PortOwner> {
+    let table = std::fs::read_to_string("/proc/net/tcp").ok()?;
+    let inode = find_listening_inode(&table, port)?;
+
+    let socket_link = format!("socket:[{}]", inode);
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let pid: u32 = match entry.file_name().to_str().and_then(|s| s.parse().ok()) {
+            Some(pid) => pid,
+            None => continue,
+        };
+        let fd_dir = entry.path().join("fd");
+        let Ok(fds) = std::fs::read_dir(&fd_dir) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            if let Ok(link) = std::fs::read_link(fd.path()) {
+                if link.to_string_lossy() == socket_link {
+                    let process_name = std::fs::read_to_string(entry.path().join("comm"))
+                        .map(|name| name.trim().to_string())
+                        .unwrap_or_else(|_| "unknown".to_string());
+                    return Some(PortOwner { pid, process_name });
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn find_port_owner(port: u16) -> Option<PortOwner> {
+    let output = Command::new("lsof")
+        .args(["-n", "-P", "-iTCP", &format!(":{}", port), "-sTCP:LISTEN"])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().nth(1)?;
+    let mut fields = line.split_whitespace();
+    let process_name = fields.next()?.to_string();
+    let pid: u32 = fields.next()?.parse().ok()?;
+    Some(PortOwner { pid, process_name })
+}
+
+#[cfg(target_os = "windows")]
+fn find_port_owner(port: u16) -> Option<PortOwner> {
+    let output = Command::new("netstat").args(["-ano", "-p", "TCP"]).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let needle = format!(":{} ", port);
+    let pid: u32 = stdout
+        .lines()
+        .find(|line| line.contains("LISTENING") && line.contains(&needle))
+        .and_then(|line| line.split_whitespace().last())
+        .and_then(|pid| pid.parse().ok())?;
+
+    let tasklist = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .ok()?;
+    let process_name = String::from_utf8_lossy(&tasklist.stdout)
+        .lines()
+        .next()
+        .and_then(|line| line.split(',').next())
+        .map(|name| name.trim_matches('"').to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    Some(PortOwner { pid, process_name })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn find_port_owner(_port: u16) -> Option<PortOwner> {
+    None
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|dur| dur.as_secs())
+        .unwrap_or(0)
+}
+
+fn is_pid_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+    #[cfg(windows)]
+    {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid)])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+            .unwrap_or(false)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        false
+    }
+}
+
+fn kill_pid(pid: u32) {
+    #[cfg(unix)]
+    {
+        let _ = Command::new("kill").arg("-9").arg(pid.to_string()).output();
+    }
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .output();
+    }
+}
+
+/// Window within which repeated crashes count toward the crash-loop
+/// breaker; a restart outside this window starts a fresh count.
+const CRASH_LOOP_WINDOW: Duration = Duration::from_secs(60);
+/// Once this many restarts happen inside `CRASH_LOOP_WINDOW`, the
+/// supervisor gives up and reports `"crashed"` instead of thrashing.
+const CRASH_LOOP_MAX_RESTARTS: u32 = 5;
+/// How long a restarted gateway has to stay up before the backoff/crash
+/// count resets to a clean slate.
+const RESTART_STABLE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Exponential backoff for the Nth restart attempt: 500ms, 1s, 2s, ...
+/// capped at 30s.
+fn restart_backoff(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(6);
+    let millis = 500u64.saturating_mul(1u64 << exponent);
+    Duration::from_millis(millis).min(Duration::from_secs(30))
+}
+
+/// Default grace period between asking a gateway process to exit and
+/// escalating to a hard kill. Overridable via `LOCAL_RUNTIME_SHUTDOWN_GRACE_SECS`
+/// for slower embedded runtimes that need more time to flush state.
+fn shutdown_grace_period() -> Duration {
+    std::env::var("LOCAL_RUNTIME_SHUTDOWN_GRACE_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(5))
+}
+
+/// Asks a process to exit on its own — `SIGTERM` on Unix, a polite
+/// `taskkill` (no `/F`) on Windows — leaving `kill_pid`/`child.kill()` as
+/// the caller's fallback once the grace period elapses.
+fn send_sigterm(pid: u32) {
+    #[cfg(unix)]
+    {
+        let _ = Command::new("kill").arg("-TERM").arg(pid.to_string()).output();
+    }
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill").args(["/PID", &pid.to_string()]).output();
+    }
+}
+
+fn wait_for_pid_exit(pid: u32, grace: Duration) -> bool {
+    let start = Instant::now();
+    loop {
+        if !is_pid_alive(pid) {
+            return true;
+        }
+        if start.elapsed() >= grace {
+            return !is_pid_alive(pid);
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+}
+
+fn wait_for_child_exit(child: &mut Child, grace: Duration) -> bool {
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => return true,
+            Ok(None) => {}
+            Err(_) => return false,
+        }
+        if start.elapsed() >= grace {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+}
+
+/// Asks `child` to exit, waits up to `grace` for it to do so on its own,
+/// and force-kills it otherwise. Returns whether it exited gracefully; for
+/// a container, `stop -t` does its own escalation, so the result is only
+/// whether that command succeeded. Shared by `stop()`, which records the
+/// result, and the readiness gate in `start_internal`, which passes a zero
+/// grace period and ignores it.
+fn terminate_child(child: GatewayChild, grace: Duration) -> bool {
+    match child {
+        GatewayChild::Python(mut child) => {
+            let pid = child.id();
+            send_sigterm(pid);
+            let graceful = wait_for_child_exit(&mut child, grace);
+            if !graceful {
+                let _ = child.kill();
+            }
+            let _ = child.wait();
+            graceful
+        }
+        GatewayChild::Sidecar(child) => {
+            let pid = child.pid();
+            send_sigterm(pid);
+            let graceful = wait_for_pid_exit(pid, grace);
+            if !graceful {
+                let _ = child.kill();
+            }
+            graceful
+        }
+        GatewayChild::Container {
+            runtime,
+            container_name,
+        } => Command::new(&runtime)
+            .args(["stop", "-t", &grace.as_secs().to_string(), &container_name])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false),
+        GatewayChild::Adopted { pid } => {
+            send_sigterm(pid);
+            let graceful = wait_for_pid_exit(pid, grace);
+            if !graceful {
+                kill_pid(pid);
+            }
+            graceful
+        }
+    }
+}
+
+fn resolve_effective_port(configured: u16) -> u16 {
+    if TcpListener::bind(("127.0.0.1", configured)).is_ok() {
+        return configured;
+    }
+    if http_get_localhost(configured, "/health").is_ok() {
+        // Something already answers our health check on this port — most likely a
+        // sibling gateway instance, so keep it rather than hopping away from it.
+        return configured;
+    }
+    for candidate in configured.saturating_add(1)..=configured.saturating_add(50) {
+        if TcpListener::bind(("127.0.0.1", candidate)).is_ok() {
+            return candidate;
+        }
+    }
+    configured
+}
+
+#[tauri::command]
+fn gateway_reclaim_port(pid: u32) -> Result<(), GatewayError> {
+    #[cfg(unix)]
+    let result = Command::new("kill").arg("-15").arg(pid.to_string()).output();
+    #[cfg(windows)]
+    let result = Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .output();
+    #[cfg(not(any(unix, windows)))]
+    let result: std::io::Result<std::process::Output> =
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "unsupported platform"));
+
+    match result {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(GatewayError::Io(String::from_utf8_lossy(&output.stderr).to_string())),
+        Err(err) => Err(GatewayError::Io(err.to_string())),
+    }
+}
+
+/// How long a single `/health`-style probe is allowed to block on the
+/// socket before giving up. The gateway is on localhost, so anything
+/// slower than this means it accepted the connection but isn't actually
+/// serving.
+const HTTP_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Parses an HTTP status line's (e.g. `"HTTP/1.1 200 OK\r\n"`) status code.
+/// Split out from `http_get_localhost` so the parsing is testable without a
+/// real socket.
+fn parse_status_code(status_line: &str) -> Result<u16, GatewayError> {
+    status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| GatewayError::Io(format!("malformed HTTP status line: {status_line:?}")))
+}
+
+/// Parses a single trimmed header line and returns the `Content-Length`
+/// value if that's what the header is. Split out from `http_get_localhost`
+/// so the parsing is testable without a real socket.
+fn parse_content_length_header(header_line: &str) -> Option<usize> {
+    let (name, value) = header_line.split_once(':')?;
+    if name.eq_ignore_ascii_case("content-length") {
+        value.trim().parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Reads a single HTTP/1.1 response off `stream`, honoring `Content-Length`
+/// and `Connection: close` framing, and returns `Err` for non-2xx status
+/// lines instead of handing the caller a 5xx body to misread as success.
 fn http_get_localhost(port: u16, path: &str) -> Result<String, GatewayError> {
-    let mut stream =
+    let stream =
         TcpStream::connect(("127.0.0.1", port)).map_err(|err| GatewayError::Io(err.to_string()))?;
+    stream
+        .set_read_timeout(Some(HTTP_PROBE_TIMEOUT))
+        .map_err(|err| GatewayError::Io(err.to_string()))?;
+    stream
+        .set_write_timeout(Some(HTTP_PROBE_TIMEOUT))
+        .map_err(|err| GatewayError::Io(err.to_string()))?;
+
     let request = format!(
         "GET {} HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nConnection: close\r\n\r\n",
         path, port
     );
+    let mut stream = stream;
     stream
         .write_all(request.as_bytes())
         .map_err(|err| GatewayError::Io(err.to_string()))?;
-    let mut response = String::new();
-    stream
-        .read_to_string(&mut response)
+
+    let mut reader = BufReader::new(stream);
+    let mut status_line = String::new();
+    reader
+        .read_line(&mut status_line)
         .map_err(|err| GatewayError::Io(err.to_string()))?;
-    if let Some((_, body)) = response.split_once("\r\n\r\n") {
-        return Ok(body.trim().to_string());
+    let status_code = parse_status_code(&status_line)?;
+
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|err| GatewayError::Io(err.to_string()))?;
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed.is_empty() {
+            break;
+        }
+        if let Some(len) = parse_content_length_header(trimmed) {
+            content_length = Some(len);
+        }
+    }
+
+    let body = match content_length {
+        Some(len) => {
+            let mut buf = vec![0u8; len];
+            reader
+                .read_exact(&mut buf)
+                .map_err(|err| GatewayError::Io(err.to_string()))?;
+            String::from_utf8_lossy(&buf).to_string()
+        }
+        None => {
+            // No Content-Length: rely on the server honoring the
+            // `Connection: close` we requested and read until EOF.
+            let mut buf = String::new();
+            reader
+                .read_to_string(&mut buf)
+                .map_err(|err| GatewayError::Io(err.to_string()))?;
+            buf
+        }
+    };
+
+    if !(200..300).contains(&status_code) {
+        return Err(GatewayError::Io(format!(
+            "gateway responded with HTTP {status_code}: {}",
+            body.trim()
+        )));
+    }
+
+    Ok(body.trim().to_string())
+}
+
+/// Post-spawn readiness gate: polls `/health` until it reports healthy or
+/// `deadline` elapses, so `start_internal` never hands back `"running"`
+/// before the server can actually answer requests.
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const READINESS_DEADLINE: Duration = Duration::from_secs(15);
+
+fn wait_for_gateway_ready(port: u16) -> bool {
+    let started = Instant::now();
+    loop {
+        if http_get_localhost(port, "/health").is_ok() {
+            return true;
+        }
+        if started.elapsed() >= READINESS_DEADLINE {
+            return false;
+        }
+        thread::sleep(READINESS_POLL_INTERVAL);
+    }
+}
+
+/// How long to wait before retrying a dropped or refused relay connection.
+const TUNNEL_RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// How long a single read may block while polling each direction of the
+/// tunnel in turn; short enough that the loop stays responsive to `stopped`
+/// and to the other direction having data ready.
+const TUNNEL_POLL_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Splits a `tls://host:port` relay URL into its host and port. The tunnel
+/// always runs over TLS, so any other (or missing) scheme is rejected here
+/// rather than being handed to `TcpStream::connect`, which only understands
+/// bare `host:port` and would otherwise fail confusingly at connect time.
+fn parse_relay_target(relay_url: &str) -> Result<(String, u16), String> {
+    let rest = relay_url.strip_prefix("tls://").ok_or_else(|| {
+        format!("unsupported relay URL {relay_url:?}: expected a tls://host:port URL")
+    })?;
+    let (host, port) = rest
+        .rsplit_once(':')
+        .ok_or_else(|| format!("relay URL {relay_url:?} is missing a port"))?;
+    if host.is_empty() {
+        return Err(format!("relay URL {relay_url:?} is missing a host"));
+    }
+    let port: u16 = port
+        .parse()
+        .map_err(|_| format!("relay URL {relay_url:?} has an invalid port"))?;
+    Ok((host.to_string(), port))
+}
+
+/// Dials `relay_url` and performs a TLS handshake over the resulting TCP
+/// connection, returning the TLS stream used for all tunnel traffic
+/// alongside a raw clone of the underlying socket that `stop_tunnel` can
+/// shut down directly without going through the TLS layer.
+fn connect_relay_tls(relay_url: &str) -> Result<(TlsStream<TcpStream>, TcpStream), String> {
+    let (host, port) = parse_relay_target(relay_url)?;
+    let tcp = TcpStream::connect((host.as_str(), port))
+        .map_err(|err| format!("relay unreachable: {err}"))?;
+    let raw_clone = tcp
+        .try_clone()
+        .map_err(|err| format!("failed to clone relay socket: {err}"))?;
+    let connector = TlsConnector::new().map_err(|err| format!("failed to build TLS connector: {err}"))?;
+    let tls = connector
+        .connect(&host, tcp)
+        .map_err(|err| format!("TLS handshake with relay failed: {err}"))?;
+    Ok((tls, raw_clone))
+}
+
+/// Background loop for an opt-in remote tunnel session: keeps dialing
+/// `relay_url` over TLS until `stopped` is set, and for each successful
+/// connection forwards bytes between the relay and the local gateway port
+/// until either side closes. The relay reads the handshake line (now inside
+/// the TLS session, not in the clear) and routes subsequent bytes to
+/// whichever peer holds the matching access code. The handshake carries
+/// only the access code, not the gateway's bearer/share token; traffic the
+/// remote peer sends is relayed to the gateway as-is.
+fn run_tunnel_loop(
+    manager: GatewayManager,
+    relay_url: String,
+    port: u16,
+    access_code: String,
+    stopped: Arc<AtomicBool>,
+) {
+    while !stopped.load(Ordering::SeqCst) {
+        match connect_relay_tls(&relay_url) {
+            Ok((mut relay, raw_clone)) => {
+                let handshake =
+                    format!("LOCAL-RUNTIME-TUNNEL 1\r\naccess-code: {access_code}\r\nport: {port}\r\n\r\n");
+                if let Err(err) = relay.write_all(handshake.as_bytes()) {
+                    manager.push_notice(format!("tunnel: handshake with relay failed: {err}"));
+                } else {
+                    match TcpStream::connect(("127.0.0.1", port)) {
+                        Ok(local) => {
+                            if let Ok(mut guard) = manager.tunnel.lock() {
+                                guard.active_stream = Some(raw_clone);
+                            }
+                            manager.push_log(format!("tunnel: connected via relay {relay_url}"));
+                            forward_tunnel_streams(relay, local);
+                            manager.push_notice("tunnel: relay connection closed".to_string());
+                        }
+                        Err(err) => {
+                            manager.push_notice(format!("tunnel: local gateway unreachable: {err}"));
+                        }
+                    }
+                }
+            }
+            Err(err) => {
+                manager.push_notice(format!("tunnel: {err}"));
+            }
+        }
+        if let Ok(mut guard) = manager.tunnel.lock() {
+            guard.active_stream = None;
+        }
+        if stopped.load(Ordering::SeqCst) {
+            return;
+        }
+        thread::sleep(TUNNEL_RECONNECT_DELAY);
     }
-    Ok(response.trim().to_string())
+}
+
+/// Forwards bytes in both directions between `relay` (TLS) and `local`
+/// (plaintext loopback) until either side closes. `native_tls::TlsStream`
+/// doesn't support splitting into independent read/write halves the way a
+/// plain `TcpStream` does, so instead of two threads this polls each
+/// direction in turn on a short read timeout; `stop_tunnel` unblocks it by
+/// shutting down the raw socket clone, which the next poll observes as EOF.
+fn forward_tunnel_streams(mut relay: TlsStream<TcpStream>, mut local: TcpStream) {
+    if relay.get_ref().set_read_timeout(Some(TUNNEL_POLL_TIMEOUT)).is_err() {
+        return;
+    }
+    if local.set_read_timeout(Some(TUNNEL_POLL_TIMEOUT)).is_err() {
+        return;
+    }
+
+    let mut buf = [0u8; 8192];
+    loop {
+        match relay.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                if local.write_all(&buf[..n]).is_err() {
+                    break;
+                }
+            }
+            Err(err) if is_timeout(&err) => {}
+            Err(_) => break,
+        }
+        match local.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                if relay.write_all(&buf[..n]).is_err() {
+                    break;
+                }
+            }
+            Err(err) if is_timeout(&err) => {}
+            Err(_) => break,
+        }
+    }
+    let _ = local.shutdown(std::net::Shutdown::Both);
+    let _ = relay.shutdown();
+}
+
+fn is_timeout(err: &std::io::Error) -> bool {
+    matches!(err.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut)
+}
+
+/// Lowest protocol number this launcher knows how to drive. Bump this
+/// alongside breaking changes to the gateway's HTTP surface; a gateway
+/// advertising anything lower is reported as `"incompatible"` instead of
+/// `"running"`.
+const MIN_SUPPORTED_PROTOCOL: u32 = 1;
+
+#[derive(Default, serde::Deserialize)]
+struct GatewayVersionInfo {
+    #[serde(default)]
+    build_version: Option<String>,
+    #[serde(default)]
+    protocol: Option<u32>,
+    #[serde(default)]
+    capabilities: Vec<String>,
+}
+
+/// Probes the running gateway for its advertised build/protocol. Tries the
+/// dedicated `/version` endpoint first and falls back to parsing the same
+/// fields out of `/health`, since older gateways only ever served the
+/// latter. Returns `None` if neither endpoint is reachable or parseable,
+/// which callers treat as "unknown, assume compatible".
+fn fetch_gateway_version(port: u16) -> Option<GatewayVersionInfo> {
+    let body = http_get_localhost(port, "/version")
+        .or_else(|_| http_get_localhost(port, "/health"))
+        .ok()?;
+    serde_json::from_str(&body).ok()
 }
 
 impl GatewayManager {
+    /// User-/frontend-facing entry point. Resets the crash-loop breaker and
+    /// backoff bookkeeping — a deliberate start is always given a fresh
+    /// attempt — then delegates to `start_internal`, which is also what the
+    /// supervisor calls for automatic restarts (without the reset).
     fn start(&self, app: &tauri::AppHandle) -> Result<StatusResponse, GatewayError> {
+        self.reset_supervision();
+        self.start_internal(app)
+    }
+
+    fn reset_supervision(&self) {
+        if let Ok(mut guard) = self.inner.lock() {
+            guard.shutting_down = false;
+            guard.crashed = false;
+            guard.restart_count = 0;
+            guard.restart_window_started = None;
+            guard.healthy_since = None;
+        }
+    }
+
+    /// Spawns a background watcher for the child just started. Once the
+    /// child disappears without `shutting_down` having been set, it hands
+    /// off to `handle_unexpected_exit` for backoff/crash-loop handling and
+    /// then exits — a fresh watcher is spawned for each (re)start.
+    fn supervise(&self, app: tauri::AppHandle) {
+        let manager = self.clone();
+        thread::spawn(move || loop {
+            thread::sleep(Duration::from_secs(1));
+            let mut guard = manager.inner.lock().expect("state lock");
+            if guard.shutting_down {
+                return;
+            }
+            Self::refresh_child_state(&mut guard);
+            if guard.child.is_some() {
+                if let Some(healthy_since) = guard.healthy_since {
+                    if healthy_since.elapsed() >= RESTART_STABLE_WINDOW {
+                        guard.restart_count = 0;
+                        guard.restart_window_started = None;
+                    }
+                }
+                continue;
+            }
+            drop(guard);
+            manager.handle_unexpected_exit(&app);
+            return;
+        });
+    }
+
+    fn handle_unexpected_exit(&self, app: &tauri::AppHandle) {
+        let mut guard = self.inner.lock().expect("state lock");
+        if guard.shutting_down {
+            return;
+        }
+        let now = Instant::now();
+        let window_start = *guard.restart_window_started.get_or_insert(now);
+        if now.duration_since(window_start) > CRASH_LOOP_WINDOW {
+            guard.restart_window_started = Some(now);
+            guard.restart_count = 0;
+        }
+        guard.restart_count += 1;
+        let attempt = guard.restart_count;
+        if attempt > CRASH_LOOP_MAX_RESTARTS {
+            guard.crashed = true;
+            drop(guard);
+            self.push_notice(format!(
+                "gateway crashed {} times within {}s; giving up on automatic restarts. Start it manually once the underlying issue is fixed.",
+                attempt,
+                CRASH_LOOP_WINDOW.as_secs()
+            ));
+            return;
+        }
+        drop(guard);
+
+        let backoff = restart_backoff(attempt);
+        self.push_notice(format!(
+            "gateway exited unexpectedly; restarting in {:?} (attempt {attempt})",
+            backoff
+        ));
+        thread::sleep(backoff);
+
+        if let Err(err) = self.start_internal(app) {
+            self.push_notice(format!("automatic restart failed: {:?}", err));
+        }
+    }
+
+    fn start_internal(&self, app: &tauri::AppHandle) -> Result<StatusResponse, GatewayError> {
         let mut guard = self.inner.lock().expect("state lock");
         Self::refresh_child_state(&mut guard);
         if guard.child.is_some() {
-            return Ok(StatusResponse {
-                status: "running".into(),
-                managed: true,
-            });
+            return Ok(self.with_tunnel_fields("running", true, Vec::new(), None));
         }
         drop(guard);
 
@@ -1089,10 +2752,7 @@ impl GatewayManager {
                         "Gateway already running on port {} (health: {body})",
                         config.port
                     ));
-                    return Ok(StatusResponse {
-                        status: "running".into(),
-                        managed: false,
-                    });
+                    return Ok(self.with_tunnel_fields("running", false, Vec::new(), None));
                 }
                 Err(error) => {
                     self.push_notice(format!(
@@ -1155,8 +2815,21 @@ impl GatewayManager {
                 let stderr = child.stderr.take();
                 let mut guard = self.inner.lock().expect("state lock");
                 guard.child = Some(GatewayChild::Python(child));
+                guard.daemon = config.daemon;
                 drop(guard);
 
+                self.write_handshake(&GatewayHandshake {
+                    pid: Some(child_pid),
+                    container_runtime: None,
+                    container_name: None,
+                    port: config.port,
+                    mode: "python".to_string(),
+                    share_token: config.share_token.clone(),
+                    started_at: now_unix_secs(),
+                    build_version: config.build_version.clone(),
+                    daemon: config.daemon,
+                });
+
                 self.push_log(format!("launcher: python gateway pid {}", child_pid));
 
                 if let Some(stream) = stdout {
@@ -1165,7 +2838,7 @@ impl GatewayManager {
                         use std::io::{BufRead, BufReader};
                         let reader = BufReader::new(stream);
                         for line in reader.lines().flatten() {
-                            manager.push_log(line);
+                            manager.push_event(LogEvent::new("gateway", line));
                         }
                     });
                 }
@@ -1176,12 +2849,29 @@ impl GatewayManager {
                         use std::io::{BufRead, BufReader};
                         let reader = BufReader::new(stream);
                         for line in reader.lines().flatten() {
-                            manager.push_log(line);
+                            manager.push_event(LogEvent::new("stderr", line));
                         }
                     });
                 }
             }
             GatewayLaunchMode::Sidecar => {
+                if let Err(message) = verify_sidecar_integrity(app, &config.build_version) {
+                    self.push_notice(format!("launcher: {message}"));
+                    let details = GatewayErrorDetails {
+                        message: message.clone(),
+                        launcher: "sidecar:local-runtime-gateway".into(),
+                        gateway_root: None,
+                        config_path: config.config_path.to_string_lossy().to_string(),
+                        args: config.args.clone(),
+                        hint: Some(
+                            "The bundled sidecar binary does not match its signed manifest digest; reinstall the app or rebuild the sidecar."
+                                .to_string(),
+                        ),
+                    };
+                    self.record_spawn_failure(&config, &details, "sidecar_integrity");
+                    return Err(GatewayError::Config(message));
+                }
+
                 let command = resolve_sidecar_command(app)?
                     .args(&config.args)
                     .env("LOCAL_RUNTIME_VERSION", &config.build_version);
@@ -1207,22 +2897,38 @@ impl GatewayManager {
                     }
                 };
 
+                let sidecar_pid = child.pid();
                 let mut guard = self.inner.lock().expect("state lock");
                 guard.child = Some(GatewayChild::Sidecar(child));
+                guard.daemon = config.daemon;
                 drop(guard);
 
+                self.write_handshake(&GatewayHandshake {
+                    pid: Some(sidecar_pid),
+                    container_runtime: None,
+                    container_name: None,
+                    port: config.port,
+                    mode: "sidecar".to_string(),
+                    share_token: config.share_token.clone(),
+                    started_at: now_unix_secs(),
+                    build_version: config.build_version.clone(),
+                    daemon: config.daemon,
+                });
+
                 self.push_log("launcher: sidecar gateway spawned");
 
                 let manager = self.clone();
                 tauri::async_runtime::spawn(async move {
                     while let Some(event) = rx.recv().await {
                         match event {
-                            CommandEvent::Stdout(line) => {
-                                manager.push_log(String::from_utf8_lossy(&line).trim().to_string())
-                            }
-                            CommandEvent::Stderr(line) => {
-                                manager.push_log(String::from_utf8_lossy(&line).trim().to_string())
-                            }
+                            CommandEvent::Stdout(line) => manager.push_event(LogEvent::new(
+                                "gateway",
+                                String::from_utf8_lossy(&line).trim().to_string(),
+                            )),
+                            CommandEvent::Stderr(line) => manager.push_event(LogEvent::new(
+                                "stderr",
+                                String::from_utf8_lossy(&line).trim().to_string(),
+                            )),
                             CommandEvent::Error(line) => {
                                 manager.push_notice(format!("sidecar error: {line}"));
                             }
@@ -1238,12 +2944,118 @@ impl GatewayManager {
                     }
                 });
             }
+            GatewayLaunchMode::Docker => {
+                let runtime = config.container_runtime.clone().ok_or_else(|| {
+                    GatewayError::Config("Missing container runtime for docker launch".into())
+                })?;
+                let container_name = format!("local-runtime-gateway-{}", config.port);
+                let mut command = Command::new(&runtime);
+                command
+                    .arg("run")
+                    .arg("-d")
+                    .args(&config.args[1..]) // skip the "run" arg already applied above
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped());
+
+                self.push_log(format!(
+                    "launcher: spawning gateway container via {} (image {})",
+                    runtime,
+                    config.container_image.clone().unwrap_or_default()
+                ));
+                let spawn_result = command.spawn();
+                let mut child = match spawn_result {
+                    Ok(child) => child,
+                    Err(err) => {
+                        self.push_notice(format!("launcher: container spawn failed: {err}"));
+                        let details = GatewayErrorDetails {
+                            message: err.to_string(),
+                            launcher: runtime.clone(),
+                            gateway_root: None,
+                            config_path: config.config_path.to_string_lossy().to_string(),
+                            args: config.args.clone(),
+                            hint: Some(
+                                "Container runtime not available; ensure docker/podman is installed and on PATH."
+                                    .to_string(),
+                            ),
+                        };
+                        self.record_spawn_failure(&config, &details, "container_spawn");
+                        return Err(GatewayError::SpawnFailed(details));
+                    }
+                };
+                let _ = child.wait();
+
+                let mut guard = self.inner.lock().expect("state lock");
+                guard.child = Some(GatewayChild::Container {
+                    runtime: runtime.clone(),
+                    container_name: container_name.clone(),
+                });
+                guard.daemon = config.daemon;
+                drop(guard);
+
+                self.write_handshake(&GatewayHandshake {
+                    pid: None,
+                    container_runtime: Some(runtime),
+                    container_name: Some(container_name.clone()),
+                    port: config.port,
+                    mode: "docker".to_string(),
+                    share_token: config.share_token.clone(),
+                    started_at: now_unix_secs(),
+                    build_version: config.build_version.clone(),
+                    daemon: config.daemon,
+                });
+
+                self.push_log(format!("launcher: container gateway named {}", container_name));
+            }
         }
 
-        Ok(StatusResponse {
-            status: "running".into(),
-            managed: true,
-        })
+        self.set_share_token(config.share_token.clone());
+        if config.share_enabled && config.share_token.is_none() {
+            self.push_notice("share mode is enabled but no token was minted".to_string());
+        }
+
+        self.push_log("launcher: waiting for gateway to report healthy");
+        if !wait_for_gateway_ready(config.port) {
+            let stderr_tail = self.stderr_tail(50);
+            self.push_notice(format!(
+                "Gateway did not become healthy within {}s; killing it",
+                READINESS_DEADLINE.as_secs()
+            ));
+            let mut guard = self.inner.lock().expect("state lock");
+            let child = guard.child.take();
+            drop(guard);
+            if let Some(child) = child {
+                let _ = terminate_child(child, Duration::from_secs(0));
+            }
+            self.clear_handshake();
+            self.set_share_token(None);
+            let details = GatewayErrorDetails {
+                message: format!(
+                    "Gateway did not respond healthy on /health within {}s",
+                    READINESS_DEADLINE.as_secs()
+                ),
+                launcher: format!("{:?}", config.mode),
+                gateway_root: config
+                    .gateway_root
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().to_string()),
+                config_path: config.config_path.to_string_lossy().to_string(),
+                args: config.args.clone(),
+                hint: Some(if stderr_tail.is_empty() {
+                    "No stderr captured; check launcher logs.".to_string()
+                } else {
+                    format!("Recent stderr:\n{stderr_tail}")
+                }),
+            };
+            self.record_spawn_failure(&config, &details, "readiness_timeout");
+            return Err(GatewayError::SpawnFailed(details));
+        }
+
+        if let Ok(mut guard) = self.inner.lock() {
+            guard.healthy_since = Some(Instant::now());
+        }
+        self.supervise(app.clone());
+
+        Ok(self.with_tunnel_fields("running", true, Vec::new(), None))
     }
 }
 
@@ -1303,6 +3115,188 @@ fn resolve_sidecar_path(app: &tauri::AppHandle) -> Option<PathBuf> {
     None
 }
 
+/// Expected sha256 digests for the bundled sidecar, keyed by target triple
+/// then build version. Shipped alongside the sidecar as a Tauri resource,
+/// together with a detached `sidecar-manifest.json.sig`; the manifest
+/// itself carries no trust until `verify_manifest_signature` confirms that
+/// signature against `SIDECAR_MANIFEST_PUBLIC_KEY_PEM`, since an attacker
+/// able to replace the sidecar binary could otherwise just as easily
+/// rewrite the manifest with a matching digest.
+#[derive(serde::Deserialize)]
+struct SidecarManifest {
+    entries: HashMap<String, HashMap<String, String>>,
+}
+
+/// Public half of the key used to sign `sidecar-manifest.json` at release
+/// time, pinned at compile time so a tampered resource directory can't also
+/// carry a forged signature. The private key lives in the release signing
+/// pipeline, not in this repo.
+const SIDECAR_MANIFEST_PUBLIC_KEY_PEM: &str = "-----BEGIN PUBLIC KEY-----
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAu1SU1LfVLPHCozMxH2Mo
+4lgOEePzNm0tRgeLezV6ffAt0gunVTLw7onLRnrq0/IzW7yWR7QkrmBL7jTKEn5u
++qKhbwKfBstIs+bMY2Zkp18gnTxKLxoS2tFczGkPLPgizskuemMghRniWaoLcyeh
+kd3qqGElvW/VDL5AaWTg0nLVkjRo9z+40RQzuVaE8AkAFmxZzow3x+VJYKdjykkJ
+0iT9wCS0DRTXu269V264Vf/3jvredZiKRkgwlL9xNAwxXFg0x/XFw005UWVRIkdg
+cKWTjpBP2dPwVZ4WWC+9aGVd+Gyn1o0CLelf4rEjGoXbAAEgAqeGUxrcIlbjXfbc
+mwIDAQAB
+-----END PUBLIC KEY-----
+";
+
+/// Loads `sidecar-manifest.json` from the resource directory, returning its
+/// path and raw bytes so callers can verify the signature over the exact
+/// bytes that were signed before parsing them.
+fn resolve_sidecar_manifest(app: &tauri::AppHandle) -> Option<(PathBuf, Vec<u8>)> {
+    let resource_dir = app.path().resource_dir().ok()?;
+    let manifest_path = resource_dir.join("sidecar-manifest.json");
+    let data = std::fs::read(&manifest_path).ok()?;
+    Some((manifest_path, data))
+}
+
+/// Verifies the detached signature at `<manifest_path>.sig` over
+/// `manifest_bytes` using the pinned release public key by shelling out to
+/// `openssl`, since none of this launcher's dependencies verify signatures. A
+/// missing or invalid signature is always a hard failure — unlike a missing
+/// manifest, which is treated as "this build doesn't ship the check".
+///
+/// The key, signature, and manifest are staged via `tempfile::NamedTempFile`
+/// (exclusive creation, process-unique names) rather than hand-rolled
+/// pid-suffixed paths under `std::env::temp_dir()` — a predictable path in a
+/// shared, world-writable temp directory is a TOCTOU/symlink hazard on a
+/// multi-user machine.
+fn verify_manifest_signature(manifest_path: &Path, manifest_bytes: &[u8]) -> Result<(), String> {
+    let sig_path = PathBuf::from(format!("{}.sig", manifest_path.display()));
+    let signature = std::fs::read(&sig_path)
+        .map_err(|err| format!("missing manifest signature {}: {err}", sig_path.display()))?;
+
+    let mut key_file =
+        NamedTempFile::new().map_err(|err| format!("failed to stage verification key: {err}"))?;
+    key_file
+        .write_all(SIDECAR_MANIFEST_PUBLIC_KEY_PEM.as_bytes())
+        .map_err(|err| format!("failed to stage verification key: {err}"))?;
+
+    let mut sig_file =
+        NamedTempFile::new().map_err(|err| format!("failed to stage manifest signature: {err}"))?;
+    sig_file
+        .write_all(&signature)
+        .map_err(|err| format!("failed to stage manifest signature: {err}"))?;
+
+    let mut manifest_file = NamedTempFile::new()
+        .map_err(|err| format!("failed to stage manifest for verification: {err}"))?;
+    manifest_file
+        .write_all(manifest_bytes)
+        .map_err(|err| format!("failed to stage manifest for verification: {err}"))?;
+
+    let output = Command::new("openssl")
+        .args(["dgst", "-sha256", "-verify"])
+        .arg(key_file.path())
+        .arg("-signature")
+        .arg(sig_file.path())
+        .arg(manifest_file.path())
+        .output()
+        .map_err(|err| format!("failed to run openssl to verify manifest signature: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "sidecar manifest signature verification failed: {}",
+            String::from_utf8_lossy(&output.stdout).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Verifies the resolved sidecar binary against the digest in
+/// `sidecar-manifest.json` for this target/build combination before
+/// `start_internal` spawns it. Missing sidecar/manifest/entry is treated as
+/// "nothing to check against" (e.g. local dev builds) rather than a hard
+/// failure. Once a manifest is present, though, it must carry a valid
+/// signature from the pinned release key — an unsigned or tampered
+/// manifest, or an actual digest mismatch, both refuse to launch.
+fn verify_sidecar_integrity(app: &tauri::AppHandle, build_version: &str) -> Result<(), String> {
+    let Some(sidecar_path) = resolve_sidecar_path(app) else {
+        return Ok(());
+    };
+    let Some((manifest_path, manifest_bytes)) = resolve_sidecar_manifest(app) else {
+        return Ok(());
+    };
+    verify_manifest_signature(&manifest_path, &manifest_bytes)?;
+    let manifest: SidecarManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|err| format!("failed to parse {}: {err}", manifest_path.display()))?;
+
+    let target = target_triple();
+    let Some(expected) = manifest
+        .entries
+        .get(target)
+        .and_then(|versions| versions.get(build_version))
+    else {
+        return Ok(());
+    };
+    let actual = sha256_hex(&sidecar_path)
+        .map_err(|err| format!("failed to hash sidecar binary {}: {err:?}", sidecar_path.display()))?;
+    if &actual != expected {
+        return Err(format!(
+            "sidecar integrity check failed for {} ({target} {build_version}): expected sha256 {expected}, got {actual}",
+            sidecar_path.display()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn sha256_hex(path: &Path) -> Result<String, GatewayError> {
+    if let Ok(output) = Command::new("sha256sum").arg(path).output() {
+        if output.status.success() {
+            return parse_hash_tool_output(&output.stdout);
+        }
+    }
+    let output = Command::new("shasum")
+        .args(["-a", "256"])
+        .arg(path)
+        .output()
+        .map_err(|err| GatewayError::Io(err.to_string()))?;
+    if !output.status.success() {
+        return Err(GatewayError::Io(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+    parse_hash_tool_output(&output.stdout)
+}
+
+#[cfg(windows)]
+fn sha256_hex(path: &Path) -> Result<String, GatewayError> {
+    let output = Command::new("certutil")
+        .arg("-hashfile")
+        .arg(path)
+        .arg("SHA256")
+        .output()
+        .map_err(|err| GatewayError::Io(err.to_string()))?;
+    if !output.status.success() {
+        return Err(GatewayError::Io(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let hex_line = text
+        .lines()
+        .nth(1)
+        .ok_or_else(|| GatewayError::Io("unexpected certutil output".into()))?;
+    Ok(hex_line.split_whitespace().collect::<String>().to_lowercase())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn sha256_hex(_path: &Path) -> Result<String, GatewayError> {
+    Err(GatewayError::Io(
+        "sha256 verification is not supported on this platform".into(),
+    ))
+}
+
+fn parse_hash_tool_output(bytes: &[u8]) -> Result<String, GatewayError> {
+    String::from_utf8_lossy(bytes)
+        .split_whitespace()
+        .next()
+        .map(|hex| hex.to_lowercase())
+        .ok_or_else(|| GatewayError::Io("unexpected hash tool output".into()))
+}
+
 #[cfg(unix)]
 fn is_executable(path: &Path) -> bool {
     std::fs::metadata(path)
@@ -1371,10 +3365,15 @@ fn main() {
             gateway_status,
             gateway_logs,
             gateway_doctor,
+            gateway_collect_diagnostics,
             gateway_models,
             save_gateway_config,
             gateway_config,
-            gateway_connection_info
+            gateway_connection_info,
+            gateway_reclaim_port,
+            start_gateway_tunnel,
+            stop_gateway_tunnel,
+            gateway_tunnel_info
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
@@ -1390,3 +3389,106 @@ fn main() {
         _ => {}
     });
 }
+
+#[cfg(test)]
+mod restart_backoff_tests {
+    use super::*;
+
+    #[test]
+    fn ramps_up_exponentially() {
+        assert_eq!(restart_backoff(1), Duration::from_millis(500));
+        assert_eq!(restart_backoff(2), Duration::from_millis(1000));
+        assert_eq!(restart_backoff(3), Duration::from_millis(2000));
+        assert_eq!(restart_backoff(4), Duration::from_millis(4000));
+    }
+
+    #[test]
+    fn caps_at_thirty_seconds() {
+        assert_eq!(restart_backoff(7), Duration::from_secs(30));
+        assert_eq!(restart_backoff(20), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn treats_attempt_zero_like_attempt_one() {
+        assert_eq!(restart_backoff(0), restart_backoff(1));
+    }
+}
+
+#[cfg(test)]
+mod port_resolution_tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    const PROC_NET_TCP_SAMPLE: &str = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 12345 1 0000000000000000 100 0 0 10 0
+   1: 00000000:0050 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 54321 1 0000000000000000 100 0 0 10 0";
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn finds_inode_for_listening_port() {
+        assert_eq!(
+            find_listening_inode(PROC_NET_TCP_SAMPLE, 0x1F90),
+            Some("12345".to_string())
+        );
+        assert_eq!(
+            find_listening_inode(PROC_NET_TCP_SAMPLE, 0x0050),
+            Some("54321".to_string())
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn returns_none_for_port_not_in_table() {
+        assert_eq!(find_listening_inode(PROC_NET_TCP_SAMPLE, 9999), None);
+    }
+
+    #[test]
+    fn keeps_configured_port_when_free() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind ephemeral port");
+        let port = listener.local_addr().expect("local addr").port();
+        drop(listener);
+        assert_eq!(resolve_effective_port(port), port);
+    }
+
+    #[test]
+    fn scans_upward_past_an_occupied_port() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind ephemeral port");
+        let port = listener.local_addr().expect("local addr").port();
+        let resolved = resolve_effective_port(port);
+        assert_ne!(resolved, port);
+        assert!(resolved > port);
+    }
+}
+
+#[cfg(test)]
+mod http_response_parsing_tests {
+    use super::*;
+
+    #[test]
+    fn parses_ok_status_code() {
+        assert_eq!(parse_status_code("HTTP/1.1 200 OK\r\n").unwrap(), 200);
+    }
+
+    #[test]
+    fn parses_error_status_code() {
+        assert_eq!(parse_status_code("HTTP/1.1 503 Service Unavailable\r\n").unwrap(), 503);
+    }
+
+    #[test]
+    fn rejects_malformed_status_line() {
+        assert!(parse_status_code("not an http response").is_err());
+    }
+
+    #[test]
+    fn extracts_content_length() {
+        assert_eq!(parse_content_length_header("Content-Length: 42"), Some(42));
+        assert_eq!(parse_content_length_header("content-length: 7"), Some(7));
+    }
+
+    #[test]
+    fn ignores_unrelated_headers() {
+        assert_eq!(parse_content_length_header("Connection: close"), None);
+        assert_eq!(parse_content_length_header("Content-Type: application/json"), None);
+    }
+}